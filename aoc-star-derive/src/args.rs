@@ -1,12 +1,41 @@
 use syn::parse::{Parse, ParseStream};
-use syn::{Ident, LitInt, Result, Token};
+use syn::{Ident, Lit, LitInt, LitStr, Result, Token};
 
 /// Struct representing the parsed arguments for the `star` macro.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct AocArgs {
     pub day: u32,
     pub part: u32,
     pub year: Option<i32>,
+    /// Name distinguishing several implementations of the same day and part.
+    pub variant: Option<String>,
+    pub example: Option<ExampleArgs>,
+    /// Time budget of the solution, in milliseconds.
+    pub timeout: Option<u64>,
+}
+
+/// Struct representing the parsed arguments for the `star_parser` macro.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParserArgs {
+    pub day: u32,
+    pub year: Option<i32>,
+}
+
+/// An example from the puzzle text, along with its expected answer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExampleArgs {
+    pub input: ExampleInput,
+    pub expected: String,
+}
+
+/// Where the input of an example comes from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExampleInput {
+    /// `example = "..."`: the input is written in the attribute.
+    Inline(String),
+    /// `example_file = "..."`: the input is read from a file, relative to the
+    /// crate root.
+    File(String),
 }
 
 impl Parse for AocArgs {
@@ -14,6 +43,10 @@ impl Parse for AocArgs {
         let mut day = None;
         let mut part = None;
         let mut year = None;
+        let mut variant = None;
+        let mut example: Option<(Ident, ExampleInput)> = None;
+        let mut expected: Option<(Ident, String)> = None;
+        let mut timeout = None;
 
         while !input.is_empty() {
             let ident: Ident = input.parse()?;
@@ -29,10 +62,55 @@ impl Parse for AocArgs {
             } else if ident == "year" {
                 let value: LitInt = input.parse()?;
                 year = Some(value.base10_parse()?);
+            } else if ident == "variant" {
+                let value: LitStr = input.parse()?;
+                if value.value().trim().is_empty() {
+                    return Err(syn::Error::new_spanned(value, "`variant` cannot be empty"));
+                }
+                variant = Some(value.value());
+            } else if ident == "example" || ident == "example_file" {
+                let value: LitStr = input.parse()?;
+                if let Some((previous, _)) = &example {
+                    let message = if *previous == ident {
+                        format!("duplicate `{ident}`")
+                    } else {
+                        "`example` and `example_file` cannot be used together".to_string()
+                    };
+                    return Err(syn::Error::new_spanned(ident, message));
+                }
+                let source = if ident == "example" {
+                    ExampleInput::Inline(value.value())
+                } else {
+                    ExampleInput::File(value.value())
+                };
+                example = Some((ident, source));
+            } else if ident == "expected" {
+                if expected.is_some() {
+                    return Err(syn::Error::new_spanned(ident, "duplicate `expected`"));
+                }
+                let value = match input.parse()? {
+                    Lit::Str(s) => s.value(),
+                    Lit::Int(i) => i.base10_digits().to_string(),
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "expected a string or integer literal",
+                        ));
+                    }
+                };
+                expected = Some((ident, value));
+            } else if ident == "timeout" {
+                let value: LitStr = input.parse()?;
+                timeout = Some(parse_millis(&value.value()).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &value,
+                        "invalid `timeout` (expected a duration such as \"500ms\", \"5s\" or \"2m\")",
+                    )
+                })?);
             } else {
                 return Err(syn::Error::new_spanned(
                     ident,
-                    "unknown attribute key (expected day, part, or year)",
+                    "unknown attribute key (expected day, part, year, variant, example, example_file, expected, or timeout)",
                 ));
             }
 
@@ -41,13 +119,88 @@ impl Parse for AocArgs {
             }
         }
 
+        let example = match (example, expected) {
+            (Some((_, input)), Some((_, expected))) => Some(ExampleArgs { input, expected }),
+            (Some((ident, _)), None) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "missing `expected` answer for the example",
+                ));
+            }
+            (None, Some((ident, _))) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`expected` requires an `example` or an `example_file`",
+                ));
+            }
+            (None, None) => None,
+        };
+
         Ok(AocArgs {
             day: day.ok_or_else(|| input.error("missing `day`"))?,
             part: part.ok_or_else(|| input.error("missing `part`"))?,
             year,
+            variant,
+            example,
+            timeout,
         })
     }
 }
+
+/// Parses a duration made of a whole number and a unit (`ms`, `s`, `m` or
+/// `h`) with no space in between, such as `"5s"`, into milliseconds.
+/// Surrounding spaces are ignored, and the duration must not be zero nor
+/// overflow. `--timeout` follows the same rules.
+fn parse_millis(duration: &str) -> Option<u64> {
+    let duration = duration.trim();
+    let split = duration.find(|c: char| !c.is_ascii_digit())?;
+    let (value, unit) = duration.split_at(split);
+    let value: u64 = value.parse().ok()?;
+    let millis = match unit {
+        "ms" => value,
+        "s" => value.checked_mul(1000)?,
+        "m" => value.checked_mul(60_000)?,
+        "h" => value.checked_mul(3_600_000)?,
+        _ => return None,
+    };
+    (millis > 0).then_some(millis)
+}
+
+impl Parse for ParserArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut day = None;
+        let mut year = None;
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            input.parse::<Token![=]>()?;
+
+            if ident == "day" {
+                let value: LitInt = input.parse()?;
+                day = Some(value.base10_parse()?);
+            } else if ident == "year" {
+                let value: LitInt = input.parse()?;
+                year = Some(value.base10_parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "unknown attribute key (expected day or year)",
+                ));
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(ParserArgs {
+            day: day.ok_or_else(|| input.error("missing `day`"))?,
+            year,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +216,9 @@ mod tests {
         assert_eq!(parsed.day, expected.day);
         assert_eq!(parsed.part, expected.part);
         assert_eq!(parsed.year, expected.year);
+        assert_eq!(parsed.variant, expected.variant);
+        assert_eq!(parsed.example, expected.example);
+        assert_eq!(parsed.timeout, expected.timeout);
     }
 
     fn assert_tests(test: &[(&str, AocArgs)]) {
@@ -91,7 +247,7 @@ mod tests {
                 AocArgs {
                     day: 1,
                     part: 1,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -99,7 +255,7 @@ mod tests {
                 AocArgs {
                     day: 25,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
         ];
@@ -116,6 +272,7 @@ mod tests {
                     day: 1,
                     part: 1,
                     year: Some(2023),
+                    ..Default::default()
                 },
             ),
             (
@@ -124,6 +281,7 @@ mod tests {
                     day: 10,
                     part: 2,
                     year: Some(2022),
+                    ..Default::default()
                 },
             ),
         ];
@@ -139,7 +297,7 @@ mod tests {
                 AocArgs {
                     day: 3,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -148,6 +306,7 @@ mod tests {
                     day: 7,
                     part: 1,
                     year: Some(2021),
+                    ..Default::default()
                 },
             ),
         ];
@@ -163,7 +322,7 @@ mod tests {
                 AocArgs {
                     day: 1,
                     part: 1,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -172,6 +331,7 @@ mod tests {
                     day: 5,
                     part: 2,
                     year: Some(2023),
+                    ..Default::default()
                 },
             ),
         ];
@@ -187,7 +347,7 @@ mod tests {
                 AocArgs {
                     day: 1,
                     part: 1,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -195,7 +355,7 @@ mod tests {
                 AocArgs {
                     day: 10,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -203,7 +363,7 @@ mod tests {
                 AocArgs {
                     day: 10,
                     part: 20,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -211,7 +371,7 @@ mod tests {
                 AocArgs {
                     day: 10,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
         ];
@@ -227,7 +387,7 @@ mod tests {
                 AocArgs {
                     day: 1,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -235,7 +395,7 @@ mod tests {
                 AocArgs {
                     day: 1,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -244,6 +404,7 @@ mod tests {
                     day: 1,
                     part: 2,
                     year: Some(2020),
+                    ..Default::default()
                 },
             ),
             (
@@ -252,6 +413,7 @@ mod tests {
                     day: 1,
                     part: 2,
                     year: Some(2020),
+                    ..Default::default()
                 },
             ),
             (
@@ -260,6 +422,7 @@ mod tests {
                     day: 1,
                     part: 2,
                     year: Some(2020),
+                    ..Default::default()
                 },
             ),
         ];
@@ -275,7 +438,7 @@ mod tests {
                 AocArgs {
                     day: 5,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -283,7 +446,7 @@ mod tests {
                 AocArgs {
                     day: 7,
                     part: 2,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -291,7 +454,7 @@ mod tests {
                 AocArgs {
                     day: 5,
                     part: 7,
-                    year: None,
+                    ..Default::default()
                 },
             ),
             (
@@ -300,6 +463,7 @@ mod tests {
                     day: 5,
                     part: 7,
                     year: Some(2021),
+                    ..Default::default()
                 },
             ),
             (
@@ -308,6 +472,7 @@ mod tests {
                     day: 5,
                     part: 7,
                     year: Some(2021),
+                    ..Default::default()
                 },
             ),
             (
@@ -316,6 +481,7 @@ mod tests {
                     day: 8,
                     part: 7,
                     year: Some(2021),
+                    ..Default::default()
                 },
             ),
         ];
@@ -344,4 +510,140 @@ mod tests {
 
         assert_fail_tests(&tests);
     }
+
+    #[test]
+    fn parses_examples() {
+        let tests = [
+            (
+                r#"day = 1, part = 1, example = "1abc2\npqr3stu8vwx", expected = "142""#,
+                AocArgs {
+                    day: 1,
+                    part: 1,
+                    example: Some(ExampleArgs {
+                        input: ExampleInput::Inline("1abc2\npqr3stu8vwx".to_string()),
+                        expected: "142".to_string(),
+                    }),
+                    ..Default::default()
+                },
+            ),
+            (
+                r#"expected = 42, day = 2, example_file = "examples/02.txt", part = 2"#,
+                AocArgs {
+                    day: 2,
+                    part: 2,
+                    example: Some(ExampleArgs {
+                        input: ExampleInput::File("examples/02.txt".to_string()),
+                        expected: "42".to_string(),
+                    }),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        assert_tests(&tests);
+    }
+
+    #[test]
+    fn parses_variants() {
+        let tests = [
+            (
+                r#"day = 9, part = 2, variant = "fast""#,
+                AocArgs {
+                    day: 9,
+                    part: 2,
+                    variant: Some("fast".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                r#"variant = "naive", day = 9, part = 2, year = 2023"#,
+                AocArgs {
+                    day: 9,
+                    part: 2,
+                    year: Some(2023),
+                    variant: Some("naive".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        assert_tests(&tests);
+        assert_fail_tests(&[
+            r#"day = 9, part = 2, variant = fast"#,
+            r#"day = 9, part = 2, variant = """#,
+            r#"day = 9, part = 2, variant = 1"#,
+        ]);
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        let timeout = |input: &str| parse(&format!("day = 1, part = 1, timeout = {input}")).timeout;
+
+        assert_eq!(timeout(r#""500ms""#), Some(500));
+        assert_eq!(timeout(r#""5s""#), Some(5_000));
+        assert_eq!(timeout(r#""2m""#), Some(120_000));
+        assert_eq!(timeout(r#""1h""#), Some(3_600_000));
+        assert_eq!(timeout(r#"" 3s ""#), Some(3_000));
+        assert_eq!(parse("day = 1, part = 1").timeout, None);
+
+        assert_fail_tests(&[
+            r#"day = 1, part = 1, timeout = 5"#,
+            r#"day = 1, part = 1, timeout = "5""#,
+            r#"day = 1, part = 1, timeout = "0s""#,
+            r#"day = 1, part = 1, timeout = "1.5s""#,
+            r#"day = 1, part = 1, timeout = "5 days""#,
+            r#"day = 1, part = 1, timeout = "5 s""#,
+            r#"day = 1, part = 1, timeout = "5124095576030432h""#,
+        ]);
+    }
+
+    #[test]
+    fn rejects_ill_formed_examples() {
+        let tests = [
+            r#"day = 1, part = 1, example = "abc""#,
+            r#"day = 1, part = 1, expected = "42""#,
+            r#"day = 1, part = 1, example = "a", example_file = "b", expected = "1""#,
+            r#"day = 1, part = 1, example = 3, expected = "1""#,
+            r#"day = 1, part = 1, example = "a", expected = 1.5"#,
+            r#"day = 1, part = 1, example = "a", example = "b", expected = "1""#,
+            r#"day = 1, part = 1, example_file = "a", example_file = "b", expected = "1""#,
+            r#"day = 1, part = 1, example = "a", expected = "1", expected = "2""#,
+        ];
+
+        assert_fail_tests(&tests);
+    }
+
+    #[test]
+    fn rejects_duplicate_examples() {
+        let error = |input: &str| parse_str::<AocArgs>(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error(r#"day = 1, part = 1, example = "a", example = "b", expected = 1"#),
+            "duplicate `example`"
+        );
+        assert_eq!(
+            error(r#"day = 1, part = 1, example = "a", expected = 1, expected = 2"#),
+            "duplicate `expected`"
+        );
+        assert_eq!(
+            error(r#"day = 1, part = 1, example_file = "a", example = "b", expected = 1"#),
+            "`example` and `example_file` cannot be used together"
+        );
+    }
+
+    #[test]
+    fn parses_parser_arguments() {
+        let parse = |input: &str| parse_str::<ParserArgs>(input);
+
+        assert_eq!(parse("day = 7").unwrap(), ParserArgs { day: 7, year: None });
+        assert_eq!(
+            parse("year = 2023, day = 7,").unwrap(),
+            ParserArgs {
+                day: 7,
+                year: Some(2023)
+            }
+        );
+        assert!(parse("year = 2023").is_err());
+        assert!(parse("day = 7, part = 1").is_err());
+    }
 }
@@ -5,6 +5,7 @@
 //!
 //!
 //! The `star` macro allows you to annotate functions as solutions for specific days and parts of Advent of Code challenges.
+//! The `star_parser` macro registers an input parser shared by the solutions of a day.
 //!
 //! ## Example
 //! ```ignore
@@ -19,14 +20,40 @@
 //! ```
 
 mod args;
+mod signature;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{ItemFn, parse_macro_input};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Expr, ExprLit, Ident, ItemFn, Lit, Meta, parse_macro_input};
 
-use crate::args::AocArgs;
+use crate::args::{AocArgs, ExampleArgs, ExampleInput, ParserArgs};
+use crate::signature::{InputKind, Signature};
 
 /// Attribute macro to mark a function as an Advent of Code solution
+///
+/// The annotated function takes the puzzle input, either owned (`String`) or
+/// borrowed (`&str`), and returns anything implementing `Display`, or a
+/// `Result` of such a value whose error converts into `Box<dyn Error + Send + Sync>`. A
+/// small shim is generated so that every solution is registered with the same
+/// `fn(String) -> Result<String, Box<dyn Error + Send + Sync>>` signature.
+///
+/// The example from the puzzle text can be given with `example = "..."` (or
+/// `example_file = "path"`, relative to the crate root) along with its
+/// `expected` answer. It is recorded in the registry, and a `#[test]` running
+/// the solution on it is generated.
+///
+/// Several implementations of the same puzzle (say a naive one and a fast one)
+/// can be registered with `variant = "name"`. The unnamed one is run by
+/// default, the others are selected with `--variant`.
+///
+/// A solution known to be slow can be given its own time budget with
+/// `timeout = "30s"` (units: `ms`, `s`, `m`, `h`), used instead of the one
+/// given with `--timeout`.
+///
+/// The registry also records the name of the function, its module, source
+/// location and doc comment, which are used to report errors and list the
+/// solutions.
+///
 /// # Example
 /// ```ignore
 /// use aoc_star_derive::star;
@@ -48,32 +75,295 @@ use crate::args::AocArgs;
 ///  // solution code here
 ///  "solution".to_string()
 ///  }
+///
+/// #[star(day = 2, part = 2)]
+/// fn solve_day2_part2(input: &str) -> u64 {
+///  // solution code here
+///  input.len() as u64
+///  }
+///
+/// #[star(day = 3, part = 1)]
+/// fn solve_day3_part1(input: &str) -> Result<u64, std::num::ParseIntError> {
+///  // errors are reported by the runner instead of panicking
+///  input.trim().parse()
+///  }
+///
+/// #[star(day = 4, part = 1, example = "1abc2\npqr3stu8vwx", expected = 142)]
+/// fn solve_day4_part1(input: &str) -> u32 {
+///  // solution code here
+///  142
+///  }
+///
+/// #[star(day = 4, part = 1, variant = "fast")]
+/// fn solve_day4_part1_fast(input: &str) -> u32 {
+///  // same answer, computed differently
+///  142
+///  }
 /// ```
 #[proc_macro_attribute]
 pub fn star(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as AocArgs);
     let input_fn = parse_macro_input!(item as ItemFn);
+    let signature = match Signature::from_fn(&input_fn) {
+        Ok(signature) => signature,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     let fn_name = &input_fn.sig.ident;
+    let shim_name = format_ident!("__aoc_star_{}", fn_name);
+    let answer = signature.answer_expr({
+        let input = signature.input_expr();
+        quote! { #fn_name(#input) }
+    });
     let day = args.day;
     let part = args.part;
     let year = match args.year {
         Some(y) => quote! { Some(#y) },
         None => quote! { None },
     };
+    let variant = match &args.variant {
+        Some(v) => quote! { Some(#v) },
+        None => quote! { None },
+    };
+    let example = match &args.example {
+        Some(example) => {
+            let input = example_input(example);
+            let expected = &example.expected;
+            quote! { Some(aoc_star::Example { input: #input, expected: #expected }) }
+        }
+        None => quote! { None },
+    };
+    let timeout = match args.timeout {
+        Some(millis) => quote! { Some(::std::time::Duration::from_millis(#millis)) },
+        None => quote! { None },
+    };
+    let doc = match doc_comment(&input_fn) {
+        Some(doc) => quote! { Some(#doc) },
+        None => quote! { None },
+    };
+    // `file!()` and `line!()` point at the annotated function rather than at
+    // the attribute
+    let name = fn_name.to_string();
+    let file = quote_spanned! {fn_name.span()=> ::std::file!() };
+    let line = quote_spanned! {fn_name.span()=> ::std::line!() };
+    let example_test = args
+        .example
+        .as_ref()
+        .map(|example| example_test(example, &args, &signature, fn_name, &shim_name));
+
+    // Solutions borrowing the parsed input get it as `&dyn Any` from the
+    // runner, and report a type mismatch with the parser as `None`
+    let (shim, func) = match &signature.input {
+        InputKind::Parsed(ty) => (
+            quote! {
+                #[doc(hidden)]
+                fn #shim_name(
+                    input: &dyn ::std::any::Any,
+                ) -> ::std::option::Option<::std::result::Result<
+                    ::std::string::String,
+                    ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+                >> {
+                    let input = input.downcast_ref::<#ty>()?;
+                    ::std::option::Option::Some(#answer)
+                }
+            },
+            quote! {
+                aoc_star::Solver::Parsed {
+                    func: #shim_name,
+                    input_type: ::std::any::type_name::<#ty>,
+                }
+            },
+        ),
+        InputKind::Owned | InputKind::Borrowed => (
+            quote! {
+                #[doc(hidden)]
+                fn #shim_name(
+                    input: ::std::string::String,
+                ) -> ::std::result::Result<
+                    ::std::string::String,
+                    ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+                > {
+                    #answer
+                }
+            },
+            quote! { aoc_star::Solver::Raw(#shim_name) },
+        ),
+    };
 
     let expanded = quote! {
         #input_fn
 
+        #shim
+
         aoc_star::inventory::submit! {
             aoc_star::AocEntry {
                 day: #day,
                 part: #part,
                 year: #year,
-                func: #fn_name,
+                variant: #variant,
+                func: #func,
+                example: #example,
+                timeout: #timeout,
+                name: #name,
+                module_path: ::std::module_path!(),
+                file: #file,
+                line: #line,
+                doc: #doc,
+            }
+        }
+
+        #example_test
+    };
+
+    expanded.into()
+}
+
+/// Attribute macro to mark a function as the input parser of an Advent of
+/// Code day.
+///
+/// The annotated function takes the puzzle input (`String` or `&str`) and
+/// returns the parsed input, or a `Result` of it. Solutions of the same day
+/// (and year, if given) taking a reference to the parsed type instead of the
+/// raw input are run on its output: the input is parsed once and shared by
+/// both parts, and parsing is timed separately from solving.
+///
+/// # Example
+/// ```ignore
+/// use aoc_star_derive::{star, star_parser};
+///
+/// struct Grid(Vec<Vec<u8>>);
+///
+/// #[star_parser(day = 7)]
+/// fn parse(input: &str) -> Grid {
+///  Grid(input.lines().map(|line| line.bytes().collect()).collect())
+///  }
+///
+/// #[star(day = 7, part = 1)]
+/// fn solve_day7_part1(grid: &Grid) -> usize {
+///  grid.0.len()
+///  }
+/// ```
+#[proc_macro_attribute]
+pub fn star_parser(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ParserArgs);
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let signature = match Signature::from_parser_fn(&input_fn) {
+        Ok(signature) => signature,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let fn_name = &input_fn.sig.ident;
+    let shim_name = format_ident!("__aoc_star_parser_{}", fn_name);
+    let parsed = signature.parsed_expr({
+        let input = signature.input_expr();
+        quote! { #fn_name(#input) }
+    });
+    let day = args.day;
+    let year = match args.year {
+        Some(y) => quote! { Some(#y) },
+        None => quote! { None },
+    };
+    let name = fn_name.to_string();
+    let file = quote_spanned! {fn_name.span()=> ::std::file!() };
+    let line = quote_spanned! {fn_name.span()=> ::std::line!() };
+
+    let expanded = quote! {
+        #input_fn
+
+        #[doc(hidden)]
+        fn #shim_name(
+            input: ::std::string::String,
+        ) -> ::std::result::Result<
+            aoc_star::ParsedInput,
+            ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+        > {
+            #parsed
+        }
+
+        aoc_star::inventory::submit! {
+            aoc_star::AocParser {
+                day: #day,
+                year: #year,
+                func: #shim_name,
+                name: #name,
+                module_path: ::std::module_path!(),
+                file: #file,
+                line: #line,
             }
         }
     };
 
     expanded.into()
 }
+
+/// The doc comment of the annotated function, without the leading space of
+/// each line. Doc attributes that are not string literals are ignored.
+fn doc_comment(item: &ItemFn) -> Option<String> {
+    let lines: Vec<String> = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(&line).to_string())
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// The `&'static str` expression holding the input of an example.
+fn example_input(example: &ExampleArgs) -> proc_macro2::TokenStream {
+    match &example.input {
+        ExampleInput::Inline(input) => quote! { #input },
+        ExampleInput::File(path) => quote! {
+            ::std::include_str!(::std::concat!(::std::env!("CARGO_MANIFEST_DIR"), "/", #path))
+        },
+    }
+}
+
+/// A test running the solution on its example and checking the answer.
+/// Solutions borrowing the parsed input are run on the output of the parser
+/// of their day.
+fn example_test(
+    example: &ExampleArgs,
+    args: &AocArgs,
+    signature: &Signature,
+    fn_name: &Ident,
+    shim_name: &Ident,
+) -> proc_macro2::TokenStream {
+    let test_name = format_ident!("__aoc_star_example_{}", fn_name);
+    let input = example_input(example);
+    let expected = &example.expected;
+    let name = fn_name.to_string();
+    let solve = match signature.input {
+        InputKind::Parsed(_) => {
+            let day = args.day;
+            let year = match args.year {
+                Some(y) => quote! { Some(#y) },
+                None => quote! { None },
+            };
+            quote! { aoc_star::__private::solve_parsed_example(#day, #year, #input, #shim_name) }
+        }
+        InputKind::Owned | InputKind::Borrowed => {
+            quote! { #shim_name(::std::string::String::from(#input)) }
+        }
+    };
+
+    quote! {
+        #[cfg(test)]
+        #[test]
+        fn #test_name() {
+            let answer = #solve
+                .unwrap_or_else(|e| ::std::panic!("`{}` failed on its example: {}", #name, e));
+            ::std::assert_eq!(answer, #expected, "wrong answer for the example of `{}`", #name);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn, Result, ReturnType, Type};
+
+/// How the puzzle input has to be handed to the annotated function.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// The function takes the input by value (`String`).
+    Owned,
+    /// The function borrows the input (`&str`, `&String`).
+    Borrowed,
+    /// The function borrows the value returned by the `star_parser` of its
+    /// day, of the given type (`&Grid`).
+    Parsed(Box<Type>),
+}
+
+/// How the value returned by the annotated function becomes an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OutputKind {
+    /// The function returns the answer directly (any `Display` type).
+    Answer,
+    /// The function returns a `Result` whose `Ok` value is the answer.
+    Fallible,
+}
+
+/// The parts of a solution signature the generated shim cares about.
+#[derive(Debug)]
+pub struct Signature {
+    pub input: InputKind,
+    pub output: OutputKind,
+}
+
+impl Signature {
+    /// Inspects the signature of the annotated function.
+    ///
+    /// Solutions must take exactly one argument (the puzzle input, or a
+    /// reference to the parsed input) and return a value; the shim takes care
+    /// of turning that value into an answer.
+    pub fn from_fn(item: &ItemFn) -> Result<Self> {
+        let sig = &item.sig;
+
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                "`star` solutions cannot be generic",
+            ));
+        }
+
+        let mut inputs = sig.inputs.iter();
+        let input = match (inputs.next(), inputs.next()) {
+            (Some(FnArg::Typed(arg)), None) => match &*arg.ty {
+                Type::Reference(reference) if reference.mutability.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        reference,
+                        "`star` solutions cannot borrow their input mutably, \
+                         take it as `&str` or `&Grid` (or `String` to own it)",
+                    ));
+                }
+                Type::Reference(reference) if is_named(&reference.elem, &["str", "String"]) => {
+                    InputKind::Borrowed
+                }
+                Type::Reference(reference) if !is_parsable(&reference.elem) => {
+                    return Err(syn::Error::new_spanned(
+                        reference,
+                        "`star` solutions borrow the parsed input as a named type \
+                         (e.g. `&Grid` or `&Vec<u8>`), not a slice, an array or a trait",
+                    ));
+                }
+                Type::Reference(reference) => InputKind::Parsed(reference.elem.clone()),
+                ty if is_named(ty, &["String"]) => InputKind::Owned,
+                ty => {
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        "`star` solutions take the puzzle input as `String` or `&str`, \
+                         or borrow the parsed input (e.g. `&Grid`)",
+                    ));
+                }
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &sig.inputs,
+                    "`star` solutions must take exactly one argument: the puzzle input",
+                ));
+            }
+        };
+
+        let output = match &sig.output {
+            ReturnType::Default => {
+                return Err(syn::Error::new_spanned(
+                    sig,
+                    "`star` solutions must return the answer",
+                ));
+            }
+            ReturnType::Type(_, ty) if is_result(ty) => OutputKind::Fallible,
+            ReturnType::Type(..) => OutputKind::Answer,
+        };
+
+        Ok(Signature { input, output })
+    }
+
+    /// Inspects the signature of a function annotated with `star_parser`.
+    ///
+    /// Parsers take the raw puzzle input, like solutions, and return the
+    /// parsed input (or a `Result` of it).
+    pub fn from_parser_fn(item: &ItemFn) -> Result<Self> {
+        let sig = &item.sig;
+
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                "`star_parser` functions cannot be generic",
+            ));
+        }
+        if sig.inputs.len() != 1 {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "`star_parser` functions must take exactly one argument: the puzzle input",
+            ));
+        }
+        if matches!(sig.output, ReturnType::Default) {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "`star_parser` functions must return the parsed input",
+            ));
+        }
+
+        let signature = Signature::from_fn(item)?;
+        if let InputKind::Parsed(ty) = &signature.input {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`star_parser` functions take the puzzle input as `String` or `&str`",
+            ));
+        }
+        Ok(signature)
+    }
+
+    /// The expression passed to the annotated function, given a binding named
+    /// `input`: the raw input as a `String`, or a reference to the parsed input.
+    pub fn input_expr(&self) -> TokenStream {
+        match self.input {
+            InputKind::Owned | InputKind::Parsed(_) => quote! { input },
+            InputKind::Borrowed => quote! { &input },
+        }
+    }
+
+    /// The body of the generated shim, turning `call` (the call to the
+    /// annotated function) into a `Result<String, Box<dyn Error + Send + Sync>>`.
+    pub fn answer_expr(&self, call: TokenStream) -> TokenStream {
+        match self.output {
+            OutputKind::Answer => quote! {
+                ::std::result::Result::Ok(::std::string::ToString::to_string(&#call))
+            },
+            OutputKind::Fallible => quote! {
+                #call
+                    .map(|answer| ::std::string::ToString::to_string(&answer))
+                    .map_err(::std::convert::Into::into)
+            },
+        }
+    }
+
+    /// The body of the generated parser shim, turning `call` (the call to the
+    /// annotated parser) into a `Result<ParsedInput, Box<dyn Error + Send + Sync>>`.
+    pub fn parsed_expr(&self, call: TokenStream) -> TokenStream {
+        match self.output {
+            OutputKind::Answer => quote! {
+                ::std::result::Result::Ok(aoc_star::ParsedInput::new(#call))
+            },
+            OutputKind::Fallible => quote! {
+                #call
+                    .map(aoc_star::ParsedInput::new)
+                    .map_err(::std::convert::Into::into)
+            },
+        }
+    }
+}
+
+/// Whether `ty` is a path whose last segment is one of `names`, such as
+/// `String` or `std::string::String`.
+fn is_named(ty: &Type, names: &[&str]) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| names.iter().any(|name| segment.ident == name)),
+        Type::Paren(paren) => is_named(&paren.elem, names),
+        Type::Group(group) => is_named(&group.elem, names),
+        _ => false,
+    }
+}
+
+/// Whether `ty` can be the type of a parsed input, which must be sized and
+/// `'static`: slices, arrays and traits (`dyn` or `impl`) are ruled out here,
+/// rather than failing inside the generated code.
+fn is_parsable(ty: &Type) -> bool {
+    match ty {
+        Type::Slice(_) | Type::Array(_) | Type::TraitObject(_) | Type::ImplTrait(_) => false,
+        Type::Paren(paren) => is_parsable(&paren.elem),
+        Type::Group(group) => is_parsable(&group.elem),
+        _ => true,
+    }
+}
+
+/// Whether `ty` names a `Result`, such as `Result<u64, E>`,
+/// `std::io::Result<u64>` or `anyhow::Result<u64>`.
+///
+/// This is purely syntactic: the macro cannot see through type aliases that
+/// are not themselves called `Result`.
+fn is_result(ty: &Type) -> bool {
+    is_named(ty, &["Result"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    fn signature(item: &str) -> Result<Signature> {
+        Signature::from_fn(&parse_str::<ItemFn>(item).unwrap())
+    }
+
+    #[test]
+    fn detects_owned_and_borrowed_inputs() {
+        let tests = [
+            ("fn f(input: String) -> String { input }", InputKind::Owned),
+            ("fn f(input: &str) -> u64 { 0 }", InputKind::Borrowed),
+            ("fn f(input: &String) -> i128 { 0 }", InputKind::Borrowed),
+            (
+                "fn f(input: String) -> impl std::fmt::Display { input }",
+                InputKind::Owned,
+            ),
+        ];
+
+        for (item, expected) in tests {
+            assert_eq!(signature(item).unwrap().input, expected, "{item}");
+        }
+    }
+
+    #[test]
+    fn detects_parsed_inputs() {
+        let tests = [
+            ("fn f(grid: &Grid) -> u64 { 0 }", "Grid"),
+            ("fn f(lines: &Vec<u32>) -> u64 { 0 }", "Vec < u32 >"),
+            ("fn f(pair: &(u32, u32)) -> u64 { 0 }", "(u32 , u32)"),
+        ];
+
+        for (item, expected) in tests {
+            let InputKind::Parsed(ty) = signature(item).unwrap().input else {
+                panic!("expected `{item}` to take a parsed input");
+            };
+            assert_eq!(quote!(#ty).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_inputs_that_cannot_be_parsed() {
+        let tests = [
+            "fn f(input: &mut String) -> u64 { 0 }",
+            "fn f(grid: &mut Grid) -> u64 { 0 }",
+            "fn f(bytes: &[u8]) -> u64 { 0 }",
+            "fn f(bytes: &[u8; 4]) -> u64 { 0 }",
+            "fn f(grid: &dyn Grid) -> u64 { 0 }",
+            "fn f(grid: &impl Grid) -> u64 { 0 }",
+        ];
+
+        for item in tests {
+            assert!(signature(item).is_err(), "{item}");
+        }
+    }
+
+    #[test]
+    fn checks_parser_signatures() {
+        let parser = |item: &str| Signature::from_parser_fn(&parse_str::<ItemFn>(item).unwrap());
+
+        assert!(parser("fn p(input: &str) -> Vec<u32> { vec![] }").is_ok());
+        assert_eq!(
+            parser("fn p(input: String) -> anyhow::Result<Grid> { todo!() }")
+                .unwrap()
+                .output,
+            OutputKind::Fallible
+        );
+        assert!(parser("fn p(grid: &Grid) -> u64 { 0 }").is_err());
+        assert!(parser("fn p(input: &str) {}").is_err());
+        assert!(parser("fn p() -> u64 { 0 }").is_err());
+    }
+
+    #[test]
+    fn detects_fallible_outputs() {
+        let tests = [
+            ("fn f(input: &str) -> u64 { 0 }", OutputKind::Answer),
+            ("fn f(input: &str) -> String { 0 }", OutputKind::Answer),
+            (
+                "fn f(input: &str) -> Result<u64, String> { Ok(0) }",
+                OutputKind::Fallible,
+            ),
+            (
+                "fn f(input: &str) -> anyhow::Result<u64> { Ok(0) }",
+                OutputKind::Fallible,
+            ),
+            (
+                "fn f(input: String) -> std::result::Result<i64, std::num::ParseIntError> { input.parse() }",
+                OutputKind::Fallible,
+            ),
+        ];
+
+        for (item, expected) in tests {
+            assert_eq!(signature(item).unwrap().output, expected, "{item}");
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_signatures() {
+        let tests = [
+            "fn f() -> String { String::new() }",
+            "fn f(a: &str, b: &str) -> String { String::new() }",
+            "fn f(input: &str) {}",
+            "fn f<T>(input: &str) -> u64 { 0 }",
+            "fn f(grid: Grid) -> u64 { 0 }",
+        ];
+
+        for item in tests {
+            assert!(signature(item).is_err(), "expected `{item}` to be rejected");
+        }
+    }
+}
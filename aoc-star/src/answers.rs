@@ -0,0 +1,236 @@
+//! Store of the known answers of every puzzle, used to catch regressions.
+//! The answers of a year are kept in `{year}.yml` in the answers directory
+//! (`answers` in the current directory, unless `answers_dir` is set in the
+//! config), keyed by day and part:
+//!
+//! ```yaml
+//! 1:
+//!   1: '142'
+//!   2: '281'
+//! ```
+//!
+//! An answer is recorded when Advent of Code accepts it, or when running a
+//! solution with `--record`. `--verify` checks the answers of a run against
+//! the recorded ones, turning every answer that differs into an
+//! [`Error::AnswerMismatch`].
+//!
+//! Answers are not keyed by variant: a puzzle has a single answer, which
+//! every variant of its solution must return. Verifying a named variant
+//! checks it against the answer recorded for the puzzle, whichever variant
+//! recorded it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use log::{debug, info, warn};
+
+use crate::Error;
+use crate::config::answers_dir;
+use crate::runner::StarRun;
+
+/// The recorded answers of a year, by day and part.
+#[derive(Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub(crate) struct Answers(BTreeMap<u32, BTreeMap<u32, String>>);
+
+impl Answers {
+    /// The file holding the answers of a year.
+    fn path(year: i32) -> Result<PathBuf, Error> {
+        Ok(answers_dir()?.join(format!("{year}.yml")))
+    }
+
+    /// Reads the answers recorded for a year, none if the file does not
+    /// exist yet.
+    pub(crate) fn load(year: i32) -> Result<Self, Error> {
+        let path = Self::path(year)?;
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        debug!("Reading the recorded answers from {}", path.display());
+        let contents = std::fs::read_to_string(&path)?;
+        serde_yaml::from_str(&contents).map_err(|source| Error::AnswersParse { path, source })
+    }
+
+    /// Writes the answers of a year, creating the answers directory if
+    /// needed.
+    pub(crate) fn save(&self, year: i32) -> Result<(), Error> {
+        let path = Self::path(year)?;
+        let contents = serde_yaml::to_string(self).map_err(|source| Error::AnswersParse {
+            path: path.clone(),
+            source,
+        })?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// The answer recorded for a day and part, if any.
+    pub(crate) fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.0.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    /// Records the answer of a day and part, returning the answer it
+    /// replaces, if it was different.
+    pub(crate) fn insert(&mut self, day: u32, part: u32, answer: &str) -> Option<String> {
+        self.0
+            .entry(day)
+            .or_default()
+            .insert(part, answer.to_string())
+            .filter(|previous| previous != answer)
+    }
+
+    /// Checks the answer of a run against the recorded one, replacing it by
+    /// an [`Error::AnswerMismatch`] if they differ. Returns whether an answer
+    /// was recorded for the run.
+    fn verify(&self, run: &mut StarRun) -> bool {
+        let Some(expected) = self.get(run.day, run.part) else {
+            return false;
+        };
+        if let Ok(found) = &run.result
+            && found != expected
+        {
+            run.result = Err(Error::AnswerMismatch {
+                day: run.day,
+                part: run.part,
+                year: run.year,
+                expected: expected.to_string(),
+                found: found.clone(),
+            });
+        }
+        true
+    }
+}
+
+/// Records the answer of a day and part of a year (e.g. once Advent of Code
+/// accepted it).
+pub(crate) fn record(year: i32, day: u32, part: u32, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::load(year)?;
+    replace(&mut answers, year, day, part, answer);
+    answers.save(year)
+}
+
+/// Records an answer, warning if it replaces a different one.
+fn replace(answers: &mut Answers, year: i32, day: u32, part: u32, answer: &str) {
+    if let Some(previous) = answers.insert(day, part, answer) {
+        warn!("Replacing the recorded answer `{previous}` of Day {day} Part {part} of Year {year}");
+    }
+}
+
+/// Records the answers of the successful runs (`--record`).
+pub(crate) fn record_runs(runs: &[StarRun]) -> Result<(), Error> {
+    for year in years(runs) {
+        let mut answers = Answers::load(year)?;
+        let mut recorded = 0;
+        for run in runs.iter().filter(|run| run.year == year) {
+            if let Ok(answer) = &run.result {
+                replace(&mut answers, year, run.day, run.part, answer);
+                recorded += 1;
+            }
+        }
+        answers.save(year)?;
+        info!("Recorded {recorded} answers of Year {year}");
+    }
+    Ok(())
+}
+
+/// Checks the answers of the runs against the recorded ones (`--verify`).
+/// Every answer that differs is replaced by an [`Error::AnswerMismatch`], so
+/// that it is reported like any other failure of the run.
+pub(crate) fn verify_runs(runs: &mut [StarRun]) -> Result<(), Error> {
+    for year in years(runs) {
+        let answers = Answers::load(year)?;
+        let mut unknown = 0;
+        for run in runs.iter_mut().filter(|run| run.year == year) {
+            if !answers.verify(run) {
+                debug!(
+                    "No recorded answer for Day {} Part {} of Year {year}",
+                    run.day, run.part
+                );
+                unknown += 1;
+            }
+        }
+        if unknown > 0 {
+            warn!("{unknown} answers of Year {year} are not recorded and were not verified");
+        }
+    }
+    Ok(())
+}
+
+/// The years of the runs, in order.
+fn years(runs: &[StarRun]) -> BTreeSet<i32> {
+    runs.iter().map(|run| run.year).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn run(day: u32, part: u32, result: Result<&str, Error>) -> StarRun {
+        StarRun {
+            day,
+            part,
+            year: 2024,
+            variant: None,
+            result: result.map(str::to_string),
+            duration: Duration::ZERO,
+            parse: None,
+            bench: None,
+        }
+    }
+
+    #[test]
+    fn reads_answers_keyed_by_day_and_part() {
+        let answers: Answers = serde_yaml::from_str("1:\n  1: '142'\n  2: 281\n3:\n  1: abc\n")
+            .expect("valid answers");
+
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), Some("281"));
+        assert_eq!(answers.get(3, 1), Some("abc"));
+        assert_eq!(answers.get(3, 2), None);
+        assert_eq!(answers.get(2, 1), None);
+
+        let yaml = serde_yaml::to_string(&answers).unwrap();
+        assert_eq!(serde_yaml::from_str::<Answers>(&yaml).unwrap(), answers);
+    }
+
+    #[test]
+    fn inserts_answers() {
+        let mut answers = Answers::default();
+
+        assert_eq!(answers.insert(1, 1, "142"), None);
+        assert_eq!(answers.insert(1, 1, "142"), None);
+        assert_eq!(answers.insert(1, 1, "143"), Some("142".to_string()));
+        assert_eq!(answers.get(1, 1), Some("143"));
+    }
+
+    #[test]
+    fn flags_answers_that_differ() {
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "142");
+        answers.insert(1, 2, "281");
+
+        let mut same = run(1, 1, Ok("142"));
+        assert!(answers.verify(&mut same));
+        assert_eq!(same.result.unwrap(), "142");
+
+        let mut different = run(1, 2, Ok("280"));
+        assert!(answers.verify(&mut different));
+        assert!(matches!(
+            different.result,
+            Err(Error::AnswerMismatch { day: 1, part: 2, year: 2024, ref expected, ref found })
+                if expected == "281" && found == "280"
+        ));
+
+        let mut failed = run(1, 2, Err(Error::DayMissing));
+        assert!(answers.verify(&mut failed));
+        assert!(matches!(failed.result, Err(Error::DayMissing)));
+
+        let mut unknown = run(2, 1, Ok("1"));
+        assert!(!answers.verify(&mut unknown));
+        assert_eq!(unknown.result.unwrap(), "1");
+    }
+}
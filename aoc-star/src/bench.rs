@@ -0,0 +1,104 @@
+//! Benchmarking of solutions (`--bench`).
+//! The solution is run several times on an already loaded input, so that the
+//! reported timings exclude input loading, network access and config reads.
+
+use std::time::Duration;
+
+use crate::runner::{DayInput, solve};
+use crate::{AocEntry, Error};
+
+/// Timing statistics over several runs of the same solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    /// Number of times the solution was run.
+    pub runs: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl BenchStats {
+    /// Computes the statistics of the given samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is empty.
+    pub(crate) fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "cannot benchmark without samples");
+        samples.sort_unstable();
+
+        let runs = samples.len() as u32;
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        BenchStats {
+            runs,
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / runs,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Runs the solution `runs` times on `input` and returns its answer along with
+/// the timing statistics, calling `ran` after each run. The first failing run
+/// aborts the benchmark. Solutions borrowing the parsed input reuse it across
+/// runs: parsing is not part of the timings.
+pub(crate) fn bench(
+    entry: &AocEntry,
+    year: i32,
+    input: &mut DayInput,
+    runs: u32,
+    mut ran: impl FnMut(),
+) -> Result<(String, BenchStats), Error> {
+    let mut answer = None;
+    let mut samples = Vec::with_capacity(runs as usize);
+    for _ in 0..runs {
+        let (result, duration) = solve(entry, year, input);
+        answer.get_or_insert(result?);
+        samples.push(duration);
+        ran();
+    }
+
+    let answer = answer.expect("at least one run is required");
+    Ok((answer, BenchStats::from_samples(samples)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::BenchStats;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_odd_number_of_samples() {
+        let stats = BenchStats::from_samples(ms(&[5, 1, 3]));
+
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn computes_stats_for_even_number_of_samples() {
+        let stats = BenchStats::from_samples(ms(&[4, 1, 10, 2]));
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_micros(4250));
+        assert_eq!(stats.max, Duration::from_millis(10));
+    }
+}
@@ -1,11 +1,220 @@
 //! Clap-based command line argument parser for the cli tool.
-//! We force the user to provide the day and part to run.
-//! If the part is not provided, it defaults to 1.
+//! Every operation is a subcommand (`run`, `bench`, `submit`, `fetch`,
+//! `new`, `test`, `list`, `validate`, `config`) with its own arguments. Running the
+//! tool without a subcommand, like `aoc-star -d 1 -p 2`, is the same as
+//! `aoc-star run -d 1 -p 2`.
+//! We force the user to provide the day to run, unless every solution is run
+//! with `--all`. If the part is not provided, it defaults to 1; `--part both`
+//! runs both parts of the day on the same input.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use log::{LevelFilter, warn};
+
+use crate::output::OutputFormat;
 
-use clap::Parser;
 #[derive(Parser, Clone, Debug)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub(crate) struct Cli {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    /// Arguments of `run`, which is used when no subcommand is given
+    #[clap(flatten)]
+    pub run: CommandArgument,
+
+    /// Only print warnings and errors on stderr
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print more details on stderr: debug messages (including those of your
+    /// solutions) with -v, and trace messages (including those of the HTTP
+    /// client) with -vv
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Deprecated spelling of `config`, kept for existing scripts
+    #[clap(long, hide = true, exclusive = true)]
+    pub setup: bool,
+}
+
+impl Cli {
+    /// Parses the command line arguments, exiting with a usage error on the
+    /// combinations clap cannot reject by itself.
+    pub(crate) fn parse_checked() -> Self {
+        let cli = Self::parse();
+        if let Err(e) = cli.check() {
+            e.exit();
+        }
+        cli
+    }
+
+    /// Checks the arguments of `run`, whether the subcommand is given or not.
+    fn check(&self) -> Result<(), clap::Error> {
+        match &self.command {
+            None => self.run.check(),
+            Some(Command::Run(command_argument)) => command_argument.check(),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// The level of the messages printed on stderr.
+    pub(crate) fn log_level(&self) -> LevelFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => LevelFilter::Warn,
+            (false, 0) => LevelFilter::Info,
+            (false, 1) => LevelFilter::Debug,
+            (false, _) => LevelFilter::Trace,
+        }
+    }
+
+    /// The given subcommand, or `run` with the top-level arguments (`config`
+    /// with the deprecated `--setup`).
+    pub(crate) fn into_command(self) -> Command {
+        if self.setup {
+            warn!("`--setup` is deprecated, use the `config` command instead");
+            return Command::Config;
+        }
+        self.command.unwrap_or(Command::Run(self.run))
+    }
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum Command {
+    /// Run a solution and print its answer (the default command)
+    Run(CommandArgument),
+    /// Run a solution several times on the same input and report timing
+    /// statistics
+    Bench(BenchArgs),
+    /// Run a solution and submit its answer to Advent of Code
+    Submit(SubmitArgs),
+    /// Download the input of a day into the input cache
+    Fetch(FetchArgs),
+    /// Create the module of a new day from a template, with a `#[star]` stub
+    /// for each part
+    New(NewArgs),
+    /// Check the solutions against the examples given in their `#[star]`
+    /// attribute
+    Test(TestArgs),
+    /// List the registered solutions, grouped by year
+    List(ListArgs),
+    /// Check that no two solutions are registered for the same day, part,
+    /// year and variant
+    Validate,
+    /// Create the config file interactively
+    Config,
+}
+
+#[derive(Args, Clone, Debug, Default)]
 pub struct CommandArgument {
-    #[clap(short, long, required_unless_present = "setup")]
+    #[clap(short, long, required_unless_present = "all")]
+    pub day: Option<u32>,
+
+    /// The part to run: 1, 2, or `both` to run both parts on the same input
+    #[clap(short, long, default_value = "1")]
+    pub part: PartSelection,
+
+    #[clap(short, long)]
+    pub year: Option<i32>,
+
+    /// Run the named variant of the solution instead of the default one
+    #[clap(long, conflicts_with = "all")]
+    pub variant: Option<String>,
+
+    /// Read the input from this file (`-` for stdin) instead of the input
+    /// cache
+    #[clap(long)]
+    pub input_file: Option<String>,
+
+    #[clap(long)]
+    pub publish: bool,
+
+    /// Run every solution registered for the year and print a summary
+    #[clap(long, conflicts_with_all = ["day", "part", "input_file", "publish"])]
+    pub all: bool,
+
+    /// Run every variant of the solution on the same input, check that they
+    /// agree and compare their timings
+    #[clap(long, requires = "day", conflicts_with_all = ["variant", "publish", "all"])]
+    pub compare: bool,
+
+    /// Run the solution N times (10 by default) on the loaded input and
+    /// report timing statistics
+    #[clap(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "10",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "publish"
+    )]
+    pub bench: Option<u32>,
+
+    /// How to print the results: human readable text, or one record per
+    /// solution as JSON lines or TSV
+    #[clap(long, value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+
+    /// Record the answers in `{year}.yml` of the answers directory, as the
+    /// known answers to check with `--verify`
+    #[clap(long, conflicts_with_all = ["verify", "compare"])]
+    pub record: bool,
+
+    /// Check the answers against the ones recorded in `{year}.yml` of the
+    /// answers directory, failing if any of them differs (a variant is
+    /// checked against the answer of its puzzle)
+    #[clap(long)]
+    pub verify: bool,
+
+    /// When Advent of Code asks to wait before submitting again, wait and
+    /// submit then instead of failing
+    #[clap(long, requires = "publish")]
+    pub wait: bool,
+
+    /// Give up on a solution that does not finish within DURATION (e.g.
+    /// `500ms`, `5s`, `2m`), unless it has its own `timeout`
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// With `--all`, run N days at once; the results are still printed in
+    /// day order
+    #[clap(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        requires = "all",
+        conflicts_with = "day"
+    )]
+    pub jobs: Option<u32>,
+
+    /// With `--jobs`, only run one solution at a time so that their timings
+    /// are not skewed by each other: only loading the inputs is concurrent
+    #[clap(long, requires = "jobs")]
+    pub serial_timing: bool,
+}
+
+impl CommandArgument {
+    /// Rejects `--part both` along with `--compare` or `--bench`, which run a
+    /// single part.
+    fn check(&self) -> Result<(), clap::Error> {
+        if self.part == PartSelection::Both && (self.compare || self.bench.is_some()) {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                "`--part both` cannot be used with `--compare` or `--bench`",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct BenchArgs {
+    #[clap(short, long, required_unless_present = "all")]
     pub day: Option<u32>,
 
     #[clap(short, long, default_value_t = 1)]
@@ -14,35 +223,412 @@ pub struct CommandArgument {
     #[clap(short, long)]
     pub year: Option<i32>,
 
+    /// Benchmark the named variant of the solution instead of the default one
+    #[clap(long, conflicts_with = "all")]
+    pub variant: Option<String>,
+
+    /// Read the input from this file (`-` for stdin) instead of the input
+    /// cache
+    #[clap(long)]
+    pub input_file: Option<String>,
+
+    /// Benchmark every solution registered for the year
+    #[clap(long, conflicts_with_all = ["day", "part", "input_file"])]
+    pub all: bool,
+
+    /// Benchmark every variant of the solution on the same input
+    #[clap(long, conflicts_with_all = ["variant", "all"])]
+    pub compare: bool,
+
+    /// How many times to run each solution
+    #[clap(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub runs: u32,
+
+    /// How to print the results: human readable text, or one record per
+    /// solution as JSON lines or TSV
+    #[clap(long, value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+
+    /// Give up on a solution whose run does not finish within DURATION
+    /// (e.g. `500ms`, `5s`, `2m`), unless it has its own `timeout`
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// With `--all`, benchmark N days at once; the results are still printed in
+    /// day order
+    #[clap(
+        short,
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        requires = "all",
+        conflicts_with = "day"
+    )]
+    pub jobs: Option<u32>,
+
+    /// With `--jobs`, only run one solution at a time so that their timings
+    /// are not skewed by each other: only loading the inputs is concurrent
+    #[clap(long, requires = "jobs")]
+    pub serial_timing: bool,
+}
+
+impl From<BenchArgs> for CommandArgument {
+    fn from(args: BenchArgs) -> Self {
+        CommandArgument {
+            day: args.day,
+            part: PartSelection::Single(args.part),
+            year: args.year,
+            variant: args.variant,
+            input_file: args.input_file,
+            all: args.all,
+            compare: args.compare,
+            bench: Some(args.runs),
+            format: args.format,
+            timeout: args.timeout,
+            jobs: args.jobs,
+            serial_timing: args.serial_timing,
+            ..CommandArgument::default()
+        }
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct SubmitArgs {
+    #[clap(short, long)]
+    pub day: u32,
+
+    /// The part to submit: 1, 2, or `both` to submit the first part that is
+    /// not solved yet
+    #[clap(short, long, default_value = "1")]
+    pub part: PartSelection,
+
+    #[clap(short, long)]
+    pub year: Option<i32>,
+
+    /// Submit the answer of the named variant of the solution
+    #[clap(long)]
+    pub variant: Option<String>,
+
+    /// Read the input from this file (`-` for stdin) instead of the input
+    /// cache
     #[clap(long)]
     pub input_file: Option<String>,
 
+    /// How to print the results: human readable text, or one record per
+    /// solution as JSON lines or TSV
+    #[clap(long, value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+
+    /// When Advent of Code asks to wait before submitting again, wait and
+    /// submit then instead of failing
     #[clap(long)]
-    pub publish: bool,
-    // Maybe in the future
-    //#[clap(long)]
-    //pub all: bool,
+    pub wait: bool,
+
+    /// Give up on a solution that does not finish within DURATION (e.g.
+    /// `500ms`, `5s`, `2m`), unless it has its own `timeout`
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+}
+
+impl From<SubmitArgs> for CommandArgument {
+    fn from(args: SubmitArgs) -> Self {
+        CommandArgument {
+            day: Some(args.day),
+            part: args.part,
+            year: args.year,
+            variant: args.variant,
+            input_file: args.input_file,
+            publish: true,
+            format: args.format,
+            wait: args.wait,
+            timeout: args.timeout,
+            ..CommandArgument::default()
+        }
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct FetchArgs {
+    #[clap(short, long)]
+    pub day: u32,
+
+    #[clap(short, long)]
+    pub year: Option<i32>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct NewArgs {
+    #[clap(short, long)]
+    pub day: u32,
+
+    #[clap(short, long)]
+    pub year: Option<i32>,
+
+    /// Also download the input of the day into the input cache
     #[clap(long)]
-    pub setup: bool,
+    pub fetch: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct TestArgs {
+    /// Only check the solutions of this day
+    #[clap(short, long)]
+    pub day: Option<u32>,
+
+    /// Only check the solutions that apply to this year
+    #[clap(short, long)]
+    pub year: Option<i32>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct ListArgs {
+    /// Only list the solutions that apply to this year
+    #[clap(short, long)]
+    pub year: Option<i32>,
+
+    /// Print the registry as a markdown table linking every solution to its
+    /// source file
+    #[clap(long)]
+    pub markdown: bool,
+}
+
+/// Parses a duration given with `--timeout`: a positive whole number followed
+/// by `ms`, `s`, `m` or `h`, with no space in between (surrounding spaces are
+/// ignored), that fits in a `u64` of milliseconds. These are the rules of `timeout` in the
+/// `#[star]` attribute, so that a budget can be moved from one to the other.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let duration = s.trim();
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(duration.len());
+    let (value, unit) = duration.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("expected a duration like `5s` or `500ms`, got `{s}`"))?;
+    let millis = match unit {
+        "ms" => Some(value),
+        "s" => value.checked_mul(1000),
+        "m" => value.checked_mul(60_000),
+        "h" => value.checked_mul(3_600_000),
+        _ => {
+            return Err(format!(
+                "unknown unit in `{s}`, expected `ms`, `s`, `m` or `h`"
+            ));
+        }
+    };
+    match millis {
+        None => Err(format!("`{s}` is too long")),
+        Some(0) => Err("the duration must not be zero".to_string()),
+        Some(millis) => Ok(Duration::from_millis(millis)),
+    }
+}
+
+/// The part(s) of a day to run, as given with `--part`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartSelection {
+    /// A single part, usually 1 or 2.
+    Single(u32),
+    /// Both parts of the day, sharing the same input (`--part both`).
+    Both,
+}
+
+impl Default for PartSelection {
+    /// Part 1, as when `--part` is not given.
+    fn default() -> Self {
+        PartSelection::Single(1)
+    }
+}
+
+impl PartSelection {
+    /// The selected part, or `None` when both parts are selected.
+    pub fn single(self) -> Option<u32> {
+        match self {
+            PartSelection::Single(part) => Some(part),
+            PartSelection::Both => None,
+        }
+    }
+}
+
+impl FromStr for PartSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("both") {
+            return Ok(PartSelection::Both);
+        }
+        s.parse()
+            .map(PartSelection::Single)
+            .map_err(|_| format!("expected a part number or `both`, got `{s}`"))
+    }
+}
+
+impl fmt::Display for PartSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartSelection::Single(part) => write!(f, "{part}"),
+            PartSelection::Both => write!(f, "both"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use clap::Parser;
+    use clap::error::ErrorKind;
+    use log::LevelFilter;
 
-    use crate::cli::CommandArgument;
+    use crate::cli::{Cli, Command, CommandArgument, PartSelection};
+    use crate::output::OutputFormat;
+
+    fn try_parse(args: &[&str]) -> Result<Command, clap::Error> {
+        let cli = Cli::try_parse_from(args)?;
+        cli.check()?;
+        Ok(cli.into_command())
+    }
+
+    fn run_args(args: &[&str]) -> CommandArgument {
+        match try_parse(args).unwrap() {
+            Command::Run(command_argument) => command_argument,
+            command => panic!("expected `run`, got {command:?}"),
+        }
+    }
 
     #[test]
     fn parses_minimal_arguments() {
         // program name + required flags
         let args = ["aoc-star", "--day", "3"];
-        let cmd = CommandArgument::parse_from(&args);
+        let cmd = run_args(&args);
 
         assert_eq!(cmd.day, Some(3));
-        assert_eq!(cmd.part, 1);
+        assert_eq!(cmd.part, PartSelection::Single(1));
         assert_eq!(cmd.year, None);
         assert_eq!(cmd.input_file, None);
         assert!(!cmd.publish);
+        assert!(!cmd.all);
+        assert_eq!(cmd.bench, None);
+        assert_eq!(cmd.format, OutputFormat::Plain);
+    }
+
+    #[test]
+    fn parses_verbosity() {
+        let level = |args: &[&str]| Cli::try_parse_from(args).unwrap().log_level();
+
+        assert_eq!(level(&["aoc-star", "-d", "1"]), LevelFilter::Info);
+        assert_eq!(level(&["aoc-star", "-q", "-d", "1"]), LevelFilter::Warn);
+        assert_eq!(
+            level(&["aoc-star", "run", "-d", "1", "-v"]),
+            LevelFilter::Debug
+        );
+        assert_eq!(level(&["aoc-star", "list", "-vv"]), LevelFilter::Trace);
+
+        assert!(Cli::try_parse_from(["aoc-star", "-q", "-v", "-d", "1"]).is_err());
+    }
+
+    #[test]
+    fn parses_output_formats() {
+        let cmd = run_args(&["aoc-star", "-d", "1", "--format", "json"]);
+        assert_eq!(cmd.format, OutputFormat::Json);
+
+        let Command::Bench(args) =
+            try_parse(&["aoc-star", "bench", "--all", "--format", "tsv"]).unwrap()
+        else {
+            panic!("expected `bench`");
+        };
+        assert_eq!(CommandArgument::from(args).format, OutputFormat::Tsv);
+
+        assert!(try_parse(&["aoc-star", "-d", "1", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn parses_record_and_verify() {
+        let cmd = run_args(&["aoc-star", "-d", "1", "--record"]);
+        assert!(cmd.record && !cmd.verify);
+
+        let cmd = run_args(&["aoc-star", "--all", "--verify"]);
+        assert!(cmd.all && cmd.verify && !cmd.record);
+
+        assert!(try_parse(&["aoc-star", "-d", "1", "--record", "--verify"]).is_err());
+        assert!(try_parse(&["aoc-star", "-d", "1", "--compare", "--record"]).is_err());
+    }
+
+    #[test]
+    fn parses_wait() {
+        let cmd = run_args(&["aoc-star", "-d", "1", "--publish", "--wait"]);
+        assert!(cmd.publish && cmd.wait);
+
+        let Command::Submit(args) =
+            try_parse(&["aoc-star", "submit", "-d", "1", "--wait"]).unwrap()
+        else {
+            panic!("expected `submit`");
+        };
+        assert!(CommandArgument::from(args).wait);
+
+        assert!(try_parse(&["aoc-star", "-d", "1", "--wait"]).is_err());
+    }
+
+    #[test]
+    fn parses_jobs() {
+        let cmd = run_args(&["aoc-star", "--all", "-j", "4", "--serial-timing"]);
+        assert_eq!(cmd.jobs, Some(4));
+        assert!(cmd.serial_timing);
+        assert_eq!(run_args(&["aoc-star", "--all"]).jobs, None);
+
+        let Command::Bench(args) =
+            try_parse(&["aoc-star", "bench", "--all", "--jobs", "2"]).unwrap()
+        else {
+            panic!("expected `bench`");
+        };
+        assert_eq!(CommandArgument::from(args).jobs, Some(2));
+
+        assert!(try_parse(&["aoc-star", "-d", "1", "-j", "2"]).is_err());
+        assert!(try_parse(&["aoc-star", "--all", "-j", "0"]).is_err());
+        assert!(try_parse(&["aoc-star", "--all", "--serial-timing"]).is_err());
+    }
+
+    #[test]
+    fn parses_timeout() {
+        let cmd = run_args(&["aoc-star", "--all", "--timeout", "5s"]);
+        assert_eq!(cmd.timeout, Some(Duration::from_secs(5)));
+        let cmd = run_args(&["aoc-star", "-d", "1", "--timeout", "250ms"]);
+        assert_eq!(cmd.timeout, Some(Duration::from_millis(250)));
+        assert_eq!(run_args(&["aoc-star", "-d", "1"]).timeout, None);
+
+        let Command::Bench(args) =
+            try_parse(&["aoc-star", "bench", "--all", "--timeout", "2m"]).unwrap()
+        else {
+            panic!("expected `bench`");
+        };
+        assert_eq!(
+            CommandArgument::from(args).timeout,
+            Some(Duration::from_secs(120))
+        );
+
+        let cmd = run_args(&["aoc-star", "-d", "1", "--timeout", " 3s "]);
+        assert_eq!(cmd.timeout, Some(Duration::from_secs(3)));
+
+        let too_long = format!("{}h", u64::MAX / 3600);
+        for invalid in ["5", "0s", "s", "5 s", "1.5s", "3d", &too_long] {
+            assert!(
+                try_parse(&["aoc-star", "-d", "1", "--timeout", invalid]).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn runs_by_default() {
+        let cmd = run_args(&["aoc-star", "run", "-d", "1", "-p", "2"]);
+        assert_eq!(cmd.day, Some(1));
+        assert_eq!(cmd.part, PartSelection::Single(2));
+
+        assert!(try_parse(&["aoc-star", "-d", "1", "list"]).is_err());
     }
 
     #[test]
@@ -59,10 +645,10 @@ mod tests {
             "input.txt",
             "--publish",
         ];
-        let cmd = CommandArgument::parse_from(&args);
+        let cmd = run_args(&args);
 
         assert_eq!(cmd.day, Some(5));
-        assert_eq!(cmd.part, 2);
+        assert_eq!(cmd.part, PartSelection::Single(2));
         assert_eq!(cmd.year, Some(2024));
         assert_eq!(cmd.input_file.as_deref(), Some("input.txt"));
         assert!(cmd.publish);
@@ -71,18 +657,170 @@ mod tests {
     #[test]
     fn parses_short_flags() {
         let args = ["aoc-star", "-d", "10", "-p", "2", "-y", "2020"];
-        let cmd = CommandArgument::parse_from(&args);
+        let cmd = run_args(&args);
 
         assert_eq!(cmd.day, Some(10));
-        assert_eq!(cmd.part, 2);
+        assert_eq!(cmd.part, PartSelection::Single(2));
         assert_eq!(cmd.year, Some(2020));
     }
 
+    #[test]
+    fn parses_all_without_day() {
+        let cmd = run_args(&["aoc-star", "--all", "--year", "2023"]);
+
+        assert!(cmd.all);
+        assert_eq!(cmd.day, None);
+        assert_eq!(cmd.year, Some(2023));
+    }
+
+    #[test]
+    fn fails_when_all_is_combined_with_day() {
+        let result = try_parse(&["aoc-star", "--all", "--day", "1"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_bench_with_and_without_runs() {
+        let cmd = run_args(&["aoc-star", "-d", "1", "--bench"]);
+        assert_eq!(cmd.bench, Some(10));
+
+        let cmd = run_args(&["aoc-star", "-d", "1", "--bench", "3"]);
+        assert_eq!(cmd.bench, Some(3));
+
+        let cmd = run_args(&["aoc-star", "--all", "--bench"]);
+        assert!(cmd.all);
+        assert_eq!(cmd.bench, Some(10));
+    }
+
+    #[test]
+    fn fails_when_bench_has_no_runs() {
+        let result = try_parse(&["aoc-star", "-d", "1", "--bench", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_list_and_validate() {
+        let Command::List(args) = try_parse(&["aoc-star", "list", "--markdown"]).unwrap() else {
+            panic!("expected `list`");
+        };
+        assert!(args.markdown);
+        assert_eq!(args.year, None);
+
+        assert!(matches!(
+            try_parse(&["aoc-star", "validate"]),
+            Ok(Command::Validate)
+        ));
+        assert!(try_parse(&["aoc-star", "list", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn parses_bench_subcommand() {
+        let Command::Bench(args) = try_parse(&["aoc-star", "bench", "-d", "3", "-n", "5"]).unwrap()
+        else {
+            panic!("expected `bench`");
+        };
+        let cmd = CommandArgument::from(args);
+        assert_eq!(cmd.day, Some(3));
+        assert_eq!(cmd.part, PartSelection::Single(1));
+        assert_eq!(cmd.bench, Some(5));
+
+        let Command::Bench(args) = try_parse(&["aoc-star", "bench", "--all"]).unwrap() else {
+            panic!("expected `bench`");
+        };
+        assert_eq!(CommandArgument::from(args).bench, Some(10));
+
+        assert!(try_parse(&["aoc-star", "bench"]).is_err());
+        assert!(try_parse(&["aoc-star", "bench", "-d", "3", "-p", "both"]).is_err());
+    }
+
+    #[test]
+    fn parses_submit_fetch_and_test() {
+        let Command::Submit(args) =
+            try_parse(&["aoc-star", "submit", "-d", "2", "-p", "both"]).unwrap()
+        else {
+            panic!("expected `submit`");
+        };
+        let cmd = CommandArgument::from(args);
+        assert!(cmd.publish);
+        assert_eq!(cmd.part, PartSelection::Both);
+
+        let Command::Fetch(args) =
+            try_parse(&["aoc-star", "fetch", "-d", "2", "-y", "2023"]).unwrap()
+        else {
+            panic!("expected `fetch`");
+        };
+        assert_eq!((args.day, args.year), (2, Some(2023)));
+        assert!(try_parse(&["aoc-star", "fetch"]).is_err());
+
+        let Command::Test(args) = try_parse(&["aoc-star", "test"]).unwrap() else {
+            panic!("expected `test`");
+        };
+        assert_eq!((args.day, args.year), (None, None));
+    }
+
+    #[test]
+    fn parses_new() {
+        let Command::New(args) = try_parse(&["aoc-star", "new", "-d", "12", "--fetch"]).unwrap()
+        else {
+            panic!("expected `new`");
+        };
+        assert_eq!((args.day, args.year, args.fetch), (12, None, true));
+        assert!(try_parse(&["aoc-star", "new", "-y", "2024"]).is_err());
+    }
+
+    #[test]
+    fn parses_variants() {
+        let cmd = run_args(&["aoc-star", "-d", "9", "--variant", "fast"]);
+        assert_eq!(cmd.variant.as_deref(), Some("fast"));
+        assert!(!cmd.compare);
+
+        let cmd = run_args(&["aoc-star", "-d", "9", "--compare", "--bench"]);
+        assert!(cmd.compare);
+        assert_eq!(cmd.bench, Some(10));
+
+        let args = ["aoc-star", "-d", "9", "--compare", "--variant", "fast"];
+        assert!(try_parse(&args).is_err());
+        assert!(try_parse(&["aoc-star", "--all", "--compare"]).is_err());
+    }
+
+    #[test]
+    fn parses_both_parts() {
+        let cmd = run_args(&["aoc-star", "-d", "4", "--part", "both"]);
+        assert_eq!(cmd.part, PartSelection::Both);
+        assert!(cmd.check().is_ok());
+
+        let cmd = run_args(&["aoc-star", "-d", "4", "-p", "both", "--publish"]);
+        assert!(cmd.publish);
+
+        assert!(try_parse(&["aoc-star", "-d", "4", "-p", "three"]).is_err());
+    }
+
+    #[test]
+    fn fails_when_both_parts_are_benchmarked_or_compared() {
+        for subcommand in [&[][..], &["run"]] {
+            for flag in ["--bench", "--compare"] {
+                let args = [&["aoc-star"], subcommand, &["-d", "4", "-p", "both", flag]].concat();
+                let error = try_parse(&args).unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::ArgumentConflict, "{args:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn forwards_setup_to_config() {
+        assert!(matches!(
+            try_parse(&["aoc-star", "--setup"]).unwrap(),
+            Command::Config
+        ));
+        assert!(try_parse(&["aoc-star", "--setup", "-d", "1"]).is_err());
+        assert!(try_parse(&["aoc-star", "config", "--setup"]).is_err());
+    }
+
     #[test]
     fn fails_when_day_is_missing() {
         // clap will exit the process on error, but we can use try_parse_from
         let args = ["aoc-star", "--part", "2"];
-        let result = CommandArgument::try_parse_from(&args);
+        let result = try_parse(&args);
         assert!(result.is_err());
     }
 }
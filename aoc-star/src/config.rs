@@ -6,16 +6,54 @@
 //! ! The config file is searched in the current directory first (aoc-star.yml),
 //! ! then in the global config directory (usually ~/.config/aoc-star/config.yml).
 //! ! If no config file is found, we create one in the global config directory
-//! ! using environment variables (AOC_TOKEN for the session cookie).
+//! ! using environment variables (AOC_TOKEN for the session cookie), but only
+//! ! when the session cookie is needed: otherwise the defaults are used and
+//! ! nothing is written.
+//!
+//! The config can also tell where the input cache and the recorded answers
+//! are, relative to the directory of the config file, unless
+//! `AOC_STAR_INPUT_DIR` (or `AOC_STAR_ANSWERS_DIR`) is set. By default, they
+//! are the `input` and `answers` directories of the current directory.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::Datelike;
+use log::{debug, info, warn};
+
+use crate::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Config {
     pub token: String,
     pub year: Option<i32>,
+    /// Directory of the input cache, relative to the directory of the config
+    /// file (`input` in the current directory by default).
+    /// `AOC_STAR_INPUT_DIR` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs_dir: Option<PathBuf>,
+    /// Path of the cached input of a day in the input cache, with the `{day}`,
+    /// `{day:02}` and `{year}` placeholders (`{day}_{year}.txt` by default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_pattern: Option<String>,
+    /// Directory of the recorded answers and of the history of submissions,
+    /// relative to the directory of the config file (`answers` in the
+    /// current directory by default). `AOC_STAR_ANSWERS_DIR` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answers_dir: Option<PathBuf>,
+    /// How `new` scaffolds the module of a day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<NewConfig>,
+}
+
+/// The `new:` section of the config, overriding where `new` creates the
+/// module of a day and what it puts in it. Both support the `{day}`,
+/// `{day:02}` and `{year}` placeholders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub(crate) struct NewConfig {
+    /// Path of the module to create, `src/days/day{day:02}.rs` by default.
+    pub path: Option<String>,
+    /// Path of a file to use as the template of the module.
+    pub template: Option<PathBuf>,
 }
 
 impl Config {
@@ -26,7 +64,14 @@ impl Config {
         let token = default_token();
         let year = Some(chrono::Utc::now().year());
 
-        Config { token, year }
+        Config {
+            token,
+            year,
+            inputs_dir: None,
+            input_pattern: None,
+            answers_dir: None,
+            new: None,
+        }
     }
 }
 
@@ -55,6 +100,21 @@ impl From<ConfigFileLocation> for PathBuf {
     }
 }
 
+/// Environment variable overriding the directory of the input cache.
+const INPUT_DIR_VAR: &str = "AOC_STAR_INPUT_DIR";
+
+/// Directory of the input cache when the config does not give one.
+const DEFAULT_INPUTS_DIR: &str = "input";
+
+/// Path of a cached input when the config does not give a pattern.
+const DEFAULT_INPUT_PATTERN: &str = "{day}_{year}.txt";
+
+/// Environment variable overriding the directory of the recorded answers.
+const ANSWERS_DIR_VAR: &str = "AOC_STAR_ANSWERS_DIR";
+
+/// Directory of the recorded answers when the config does not give one.
+const DEFAULT_ANSWERS_DIR: &str = "answers";
+
 /// Get the default token from the environment variable AOC_TOKEN
 fn default_token() -> String {
     std::env::var("AOC_TOKEN").unwrap_or_default()
@@ -66,7 +126,7 @@ fn default_token() -> String {
 ///
 /// This function is only used when the "aoc-client" feature is enabled
 /// and the user wants to fetch input or submit answers.
-pub(crate) fn get_config_token() -> Result<String, Box<dyn std::error::Error>> {
+pub(crate) fn get_config_token() -> Result<String, Error> {
     let token = get_config()?.token;
     if !token.is_empty() {
         Ok(token)
@@ -77,19 +137,122 @@ pub(crate) fn get_config_token() -> Result<String, Box<dyn std::error::Error>> {
     }
 }
 
-/// Get the config from the current directory or global config
-pub(crate) fn get_config() -> Result<Config, Box<dyn std::error::Error>> {
-    let config_path = get_config_path();
-    match config_path {
-        Some(loc) => {
-            let path: PathBuf = loc.into();
-            let contents = std::fs::read_to_string(path)?;
-            serde_yaml::from_str(&contents).map_err(|e| e.into())
-        }
+/// Get the config from the current directory or global config, creating the
+/// global config if there is none yet
+pub(crate) fn get_config() -> Result<Config, Error> {
+    match get_config_path() {
+        Some(loc) => read_config_file(loc.into()),
         None => setup_config(),
     }
 }
 
+/// Get the config from the current directory or global config, or the
+/// default one if there is none. Unlike [`get_config`], this never writes a
+/// config file.
+pub(crate) fn read_config() -> Result<Config, Error> {
+    match get_config_path() {
+        Some(loc) => read_config_file(loc.into()),
+        None => Ok(Config::default()),
+    }
+}
+
+fn read_config_file(path: PathBuf) -> Result<Config, Error> {
+    debug!("Reading the config from {}", path.display());
+    let contents = std::fs::read_to_string(&path)?;
+    serde_yaml::from_str(&contents).map_err(|source| Error::ConfigParse { path, source })
+}
+
+/// Path of the cached input of `day` for `year`: the input pattern of the
+/// config in the directory given by `AOC_STAR_INPUT_DIR`, or else by the
+/// config, relative to the directory of the config file. Reading the config
+/// never creates it.
+fn input_cache_path(day: u32, year: i32) -> Result<PathBuf, Error> {
+    let config = read_config()?;
+    let env_dir = std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    Ok(cache_path(&config, &config_dir(), env_dir, day, year))
+}
+
+/// Directory of the recorded answers and of the history of submissions: the
+/// one given by `AOC_STAR_ANSWERS_DIR`, or else by the config, relative to
+/// the directory of the config file. Reading the config never creates it.
+pub(crate) fn answers_dir() -> Result<PathBuf, Error> {
+    let config = read_config()?;
+    let env_dir = std::env::var_os(ANSWERS_DIR_VAR).map(PathBuf::from);
+    Ok(resolve_dir(
+        config.answers_dir.as_deref(),
+        &config_dir(),
+        env_dir,
+        DEFAULT_ANSWERS_DIR,
+    ))
+}
+
+/// Directory of the config file in use, empty (the current directory) if
+/// there is none.
+fn config_dir() -> PathBuf {
+    get_config_path()
+        .map(PathBuf::from)
+        .and_then(|path| path.parent().map(PathBuf::from))
+        .unwrap_or_default()
+}
+
+/// Where the cached input of `day` for `year` is looked for: its path in the
+/// input cache, where it is downloaded, then the default path in the current
+/// directory, in case the cache was moved.
+pub(crate) fn input_cache_paths(day: u32, year: i32) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = vec![input_cache_path(day, year)?];
+    let default = Path::new(DEFAULT_INPUTS_DIR).join(render(DEFAULT_INPUT_PATTERN, day, year));
+    if !paths.contains(&default) {
+        paths.push(default);
+    }
+    Ok(paths)
+}
+
+/// Path of the cached input of `day` for `year`, given the directory of the
+/// config file and the value of `AOC_STAR_INPUT_DIR`. Without either of them
+/// or `inputs_dir`, it is in `input` in the current directory.
+fn cache_path(
+    config: &Config,
+    config_dir: &Path,
+    env_dir: Option<PathBuf>,
+    day: u32,
+    year: i32,
+) -> PathBuf {
+    let dir = resolve_dir(
+        config.inputs_dir.as_deref(),
+        config_dir,
+        env_dir,
+        DEFAULT_INPUTS_DIR,
+    );
+    let pattern = config
+        .input_pattern
+        .as_deref()
+        .unwrap_or(DEFAULT_INPUT_PATTERN);
+    dir.join(render(pattern, day, year))
+}
+
+/// A directory given by an environment variable (`env_dir`), or else by the
+/// config (`configured`, relative to the directory of the config file), or
+/// else `default` in the current directory.
+fn resolve_dir(
+    configured: Option<&Path>,
+    config_dir: &Path,
+    env_dir: Option<PathBuf>,
+    default: &str,
+) -> PathBuf {
+    env_dir.unwrap_or_else(|| match configured {
+        Some(dir) => config_dir.join(dir),
+        None => PathBuf::from(default),
+    })
+}
+
+/// Replaces the `{day}`, `{day:02}` and `{year}` placeholders of `pattern`.
+pub(crate) fn render(pattern: &str, day: u32, year: i32) -> String {
+    pattern
+        .replace("{day:02}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+        .replace("{year}", &year.to_string())
+}
+
 /// Finds the current config file. First it looks for aoc-star.yml on the current directory
 /// and if nto it defaults to  ~/.config/aoc-star/config.yml.
 fn get_config_path() -> Option<ConfigFileLocation> {
@@ -110,7 +273,7 @@ fn get_config_path() -> Option<ConfigFileLocation> {
 }
 
 // Sets up the config file in the global config directory if there is no config file yet
-fn setup_config() -> Result<Config, Box<dyn std::error::Error>> {
+fn setup_config() -> Result<Config, Error> {
     let path: PathBuf = ConfigFileLocation::GlobalDir.into();
 
     if path.exists() {
@@ -118,35 +281,33 @@ fn setup_config() -> Result<Config, Box<dyn std::error::Error>> {
     }
 
     let config = Config::from_env();
+    info!("Creating a default config in {}", path.display());
     write_config(&config, ConfigFileLocation::GlobalDir)?;
     Ok(config)
 }
 
-fn write_config(
-    config: &Config,
-    location: ConfigFileLocation,
-) -> Result<(), Box<dyn std::error::Error>> {
+fn write_config(config: &Config, location: ConfigFileLocation) -> Result<(), Error> {
     let path: PathBuf = location.into();
-    let contents = serde_yaml::to_string(config)?;
+    let contents = serde_yaml::to_string(config).map_err(|source| Error::ConfigParse {
+        path: path.clone(),
+        source,
+    })?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
     std::fs::write(path, contents)?;
     Ok(())
 }
 
-fn update_token(
-    token: String,
-    location: ConfigFileLocation,
-) -> Result<(), Box<dyn std::error::Error>> {
+fn update_token(token: String, location: ConfigFileLocation) -> Result<(), Error> {
     let mut config = get_config()?;
     config.token = token;
-    let path: PathBuf = location.into();
-    let contents = serde_yaml::to_string(&config)?;
-    std::fs::write(path, contents)?;
-    Ok(())
+    write_config(&config, location)
 }
 
-pub(crate) fn setup_config_prompt() -> Result<(), Box<dyn std::error::Error>> {
+pub(crate) fn setup_config_prompt() -> Result<(), Error> {
     if config_file_exists() {
-        println!("Config file already exists. Aborting setup.");
+        warn!("Config file already exists. Aborting setup.");
         return Ok(());
     }
     let token = prompt_for_token()?;
@@ -156,22 +317,22 @@ pub(crate) fn setup_config_prompt() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn prompt_for_token() -> Result<String, Box<dyn std::error::Error>> {
+fn prompt_for_token() -> Result<String, Error> {
     use std::io::{self, Write};
 
-    print!("Enter your Advent of Code session token: ");
-    io::stdout().flush()?;
+    eprint!("Enter your Advent of Code session token: ");
+    io::stderr().flush()?;
 
     let mut token = String::new();
     io::stdin().read_line(&mut token)?;
     Ok(token.trim().to_string())
 }
 
-fn prompt_for_year() -> Result<i32, Box<dyn std::error::Error>> {
+fn prompt_for_year() -> Result<i32, Error> {
     use std::io::{self, Write};
 
-    print!("Enter the default Advent of Code year (e.g., 2024) or leave blank for current year: ");
-    io::stdout().flush()?;
+    eprint!("Enter the default Advent of Code year (e.g., 2024) or leave blank for current year: ");
+    io::stderr().flush()?;
 
     let mut year_input = String::new();
     io::stdin().read_line(&mut year_input)?;
@@ -179,8 +340,9 @@ fn prompt_for_year() -> Result<i32, Box<dyn std::error::Error>> {
     if year_input.is_empty() {
         Ok(chrono::Utc::now().year())
     } else {
-        let year: i32 = year_input.parse()?;
-        Ok(year)
+        year_input
+            .parse()
+            .map_err(|_| Error::InvalidYear(year_input.to_string()))
     }
 }
 
@@ -188,10 +350,99 @@ pub fn config_file_exists() -> bool {
     get_config_path().is_some()
 }
 
-pub fn create_global_config(
-    token: String,
-    year: Option<i32>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let config = Config { token, year };
+pub fn create_global_config(token: String, year: Option<i32>) -> Result<(), Error> {
+    let config = Config {
+        token,
+        year,
+        inputs_dir: None,
+        input_pattern: None,
+        answers_dir: None,
+        new: None,
+    };
     write_config(&config, ConfigFileLocation::GlobalDir)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{Config, cache_path, render, resolve_dir};
+
+    fn config(inputs_dir: Option<&str>, input_pattern: Option<&str>) -> Config {
+        Config {
+            token: String::new(),
+            year: None,
+            inputs_dir: inputs_dir.map(PathBuf::from),
+            input_pattern: input_pattern.map(str::to_string),
+            answers_dir: None,
+            new: None,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        assert_eq!(render("day{day:02}.rs", 7, 2024), "day07.rs");
+        assert_eq!(render("{year}/d{day}.txt", 12, 2023), "2023/d12.txt");
+    }
+
+    #[test]
+    fn resolves_the_input_cache_relative_to_the_config() {
+        let home = Path::new("/home/me/.config/aoc-star");
+
+        assert_eq!(
+            cache_path(&config(None, None), Path::new(""), None, 3, 2024),
+            Path::new("input/3_2024.txt")
+        );
+        assert_eq!(
+            cache_path(&config(None, Some("day{day:02}.txt")), home, None, 3, 2024),
+            Path::new("input/day03.txt")
+        );
+        assert_eq!(
+            cache_path(&config(Some("inputs"), None), home, None, 3, 2024),
+            home.join("inputs/3_2024.txt")
+        );
+        assert_eq!(
+            cache_path(
+                &config(Some("/data/aoc"), Some("{year}/day{day:02}.txt")),
+                home,
+                None,
+                3,
+                2024
+            ),
+            Path::new("/data/aoc/2024/day03.txt")
+        );
+        assert_eq!(
+            cache_path(
+                &config(Some("inputs"), None),
+                home,
+                Some(PathBuf::from("ci/inputs")),
+                3,
+                2024
+            ),
+            Path::new("ci/inputs/3_2024.txt")
+        );
+    }
+
+    #[test]
+    fn resolves_directories_relative_to_the_config() {
+        let home = Path::new("/home/me/.config/aoc-star");
+
+        assert_eq!(
+            resolve_dir(None, home, None, "answers"),
+            Path::new("answers")
+        );
+        assert_eq!(
+            resolve_dir(Some(Path::new("aoc/answers")), home, None, "answers"),
+            home.join("aoc/answers")
+        );
+        assert_eq!(
+            resolve_dir(
+                Some(Path::new("aoc/answers")),
+                home,
+                Some(PathBuf::from("ci")),
+                "answers"
+            ),
+            Path::new("ci")
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Error type returned by the runner.
+//! Every failure the runner can hit is a variant of [`Error`], so that callers
+//! can match on the cause (and pick an exit code with [`Error::exit_code`])
+//! instead of catching panics.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::AocParser;
+use crate::registry::Conflict;
+
+/// Error returned by [`run`](crate::run) and the runner.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// No solution is registered for the requested day, part and year.
+    NoSolution { day: u32, part: u32, year: i32 },
+    /// A [`Runner`](crate::Runner) was run without setting the day.
+    DayMissing,
+    /// Both parts were requested (`--part both`) where a single part is
+    /// required, such as with `--compare` or `--bench`.
+    SinglePartRequired,
+    /// Several solutions are registered for the same day, part and year.
+    ConflictingSolutions(Vec<Conflict>),
+    /// A solution borrows the parsed input, but no `#[star_parser]` is
+    /// registered for its day and year (`None` for year-agnostic ones).
+    NoParser { day: u32, year: Option<i32> },
+    /// Several parsers are registered for the same day and year.
+    ConflictingParsers {
+        day: u32,
+        year: Option<i32>,
+        parsers: Vec<&'static AocParser>,
+    },
+    /// The parser of a day returned a value of another type than the one the
+    /// solution borrows.
+    ParsedInputMismatch {
+        day: u32,
+        part: u32,
+        /// Path of the solution, e.g. `my_crate::day07::part1`.
+        solution: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The parser ran but returned an error.
+    Parse {
+        day: u32,
+        year: i32,
+        /// Path of the parser that failed, e.g. `my_crate::day07::parse`.
+        parser: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// Solutions are registered for the requested day and part, but not as the
+    /// requested variant (`None` for the default one).
+    UnknownVariant {
+        day: u32,
+        part: u32,
+        year: i32,
+        variant: Option<String>,
+        /// The registered variants, `default` standing for the unnamed one.
+        available: Vec<String>,
+    },
+    /// The variants of a solution returned different answers on the same
+    /// input.
+    VariantsDisagree {
+        day: u32,
+        part: u32,
+        year: i32,
+        /// The answer of each variant, `default` standing for the unnamed one.
+        answers: Vec<(String, String)>,
+    },
+    /// The solution ran but returned an error.
+    Solution {
+        day: u32,
+        part: u32,
+        year: i32,
+        /// Path of the function that failed, e.g. `my_crate::day01::part1`.
+        solution: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The solution (or the parser of its day) panicked. The rest of the
+    /// batch still runs.
+    Panic {
+        day: u32,
+        part: u32,
+        year: i32,
+        /// Path of the solution, e.g. `my_crate::day01::part1`.
+        solution: String,
+        message: String,
+        /// Where the panic happened, e.g. `src/day01.rs:12:5`.
+        location: Option<String>,
+    },
+    /// The solution (or the parser of its day) did not finish within its time
+    /// budget (`--timeout`, or `timeout` in its `#[star]` attribute). With
+    /// `--bench`, the budget applies to each run. It was left running in the
+    /// background (see the `worker` module for what that costs).
+    Timeout {
+        day: u32,
+        part: u32,
+        year: i32,
+        /// Path of the solution, e.g. `my_crate::day01::part1`.
+        solution: String,
+        budget: Duration,
+    },
+    /// A solution returned another answer than the one recorded for its day
+    /// and part (`--verify`).
+    AnswerMismatch {
+        day: u32,
+        part: u32,
+        year: i32,
+        expected: String,
+        found: String,
+    },
+    /// Some solutions did not return the expected answer on their example
+    /// (`test`).
+    ExamplesFailed { failed: usize, total: usize },
+    /// No input file was given, the input is not in the input cache, and it
+    /// could not be downloaded.
+    InputUnavailable {
+        day: u32,
+        year: i32,
+        /// The locations where the input was looked for.
+        tried: Vec<PathBuf>,
+        /// Why downloading the input failed, `None` if remote input is
+        /// disabled (the `aoc-client` feature is off).
+        download: Option<Box<Error>>,
+    },
+    /// The input file could not be read.
+    InputRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Publishing was requested but the `aoc-client` feature is off.
+    PublishUnavailable,
+    /// A required configuration value (such as the session cookie) is missing.
+    ConfigMissing { key: &'static str },
+    /// The config file could not be parsed or written.
+    ConfigParse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    /// The file of recorded answers of a year could not be parsed or written.
+    AnswersParse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    /// The year entered while setting up the config is not a number.
+    InvalidYear(String),
+    /// Communication with Advent of Code failed.
+    Network(Box<dyn std::error::Error + Send + Sync>),
+    /// Advent of Code did not accept the submitted answer.
+    SubmissionRejected {
+        day: u32,
+        part: u32,
+        year: i32,
+        reason: Rejection,
+    },
+    /// The answer was not submitted, since a previous submission of the same
+    /// part shows that it is wrong: the same answer was rejected, or
+    /// `previous` was already too high (or too low) and the answer is not
+    /// lower (or higher).
+    KnownWrongAnswer {
+        day: u32,
+        part: u32,
+        year: i32,
+        answer: String,
+        previous: String,
+        reason: Rejection,
+    },
+    /// Advent of Code asked to wait before submitting another answer for the
+    /// puzzle, and `remaining` is left to wait.
+    SubmissionLocked {
+        day: u32,
+        year: i32,
+        remaining: Duration,
+    },
+    /// The history of submissions could not be parsed.
+    SubmissionsParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// `new` was asked to create a file that already exists.
+    FileExists(PathBuf),
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+}
+
+/// Why Advent of Code rejected a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The answer is wrong.
+    Incorrect,
+    /// The answer is wrong, and too high.
+    TooHigh,
+    /// The answer is wrong, and too low.
+    TooLow,
+    /// An answer was submitted too recently; the submission was not checked.
+    TooRecent,
+    /// The part is either already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Error {
+    /// A process exit code describing the cause of the error.
+    ///
+    /// | Code | Cause                                           |
+    /// |------|-------------------------------------------------|
+    /// | 1    | I/O error, file to create already exists, or    |
+    /// |      | invalid file of recorded answers or submissions |
+    /// | 2    | no solution (or parser) registered, no day, or  |
+    /// |      | both parts where a single one is required       |
+    /// | 3    | input or publishing unavailable (when the input |
+    /// |      | could not be downloaded, the code of the cause) |
+    /// | 4    | configuration missing or invalid                |
+    /// | 5    | network error                                   |
+    /// | 6    | submission rejected, known to be wrong or too   |
+    /// |      | early                                           |
+    /// | 7    | the solution or its parser returned an error    |
+    /// |      | or panicked, or an example was not solved       |
+    /// | 8    | conflicting or mismatched registrations         |
+    /// | 9    | variants of a solution disagree                 |
+    /// | 10   | an answer differs from the recorded one         |
+    /// | 11   | a solution exceeded its time budget             |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InputUnavailable {
+                download: Some(cause),
+                ..
+            } => cause.exit_code(),
+            Error::Io(_)
+            | Error::InputRead { .. }
+            | Error::FileExists(_)
+            | Error::AnswersParse { .. }
+            | Error::SubmissionsParse { .. } => 1,
+            Error::NoSolution { .. }
+            | Error::DayMissing
+            | Error::SinglePartRequired
+            | Error::UnknownVariant { .. }
+            | Error::NoParser { .. } => 2,
+            Error::InputUnavailable { .. } | Error::PublishUnavailable => 3,
+            Error::ConfigMissing { .. } | Error::ConfigParse { .. } | Error::InvalidYear(_) => 4,
+            Error::Network(_) => 5,
+            Error::SubmissionRejected { .. }
+            | Error::KnownWrongAnswer { .. }
+            | Error::SubmissionLocked { .. } => 6,
+            Error::Solution { .. }
+            | Error::Parse { .. }
+            | Error::Panic { .. }
+            | Error::ExamplesFailed { .. } => 7,
+            Error::ConflictingSolutions(_)
+            | Error::ConflictingParsers { .. }
+            | Error::ParsedInputMismatch { .. } => 8,
+            Error::VariantsDisagree { .. } => 9,
+            Error::AnswerMismatch { .. } => 10,
+            Error::Timeout { .. } => 11,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSolution { day, part, year } => {
+                write!(
+                    f,
+                    "No solution found for Day {day} Part {part} of Year {year}"
+                )
+            }
+            Error::DayMissing => write!(f, "No day was given to run"),
+            Error::SinglePartRequired => {
+                write!(f, "A single part is required, `both` cannot be used here")
+            }
+            Error::ConflictingSolutions(conflicts) => match conflicts.as_slice() {
+                [conflict] => write!(f, "Conflicting solutions registered for {conflict}"),
+                conflicts => {
+                    write!(f, "Conflicting solutions registered:")?;
+                    for conflict in conflicts {
+                        write!(f, "\n  {conflict}")?;
+                    }
+                    Ok(())
+                }
+            },
+            Error::NoParser {
+                day,
+                year: Some(year),
+            } => write!(f, "No parser found for Day {day} of Year {year}"),
+            Error::NoParser { day, year: None } => {
+                write!(f, "No year-agnostic parser found for Day {day}")
+            }
+            Error::ConflictingParsers { day, year, parsers } => {
+                let parsers: Vec<String> = parsers
+                    .iter()
+                    .map(|parser| {
+                        format!(
+                            "`{}` ({}:{})",
+                            parser.qualified_name(),
+                            parser.file,
+                            parser.line
+                        )
+                    })
+                    .collect();
+                match year {
+                    Some(year) => write!(
+                        f,
+                        "Conflicting parsers registered for Day {day} of Year {year}: {}",
+                        parsers.join(", ")
+                    ),
+                    None => write!(
+                        f,
+                        "Conflicting parsers registered for Day {day} (year-agnostic): {}",
+                        parsers.join(", ")
+                    ),
+                }
+            }
+            Error::ParsedInputMismatch {
+                day,
+                part,
+                solution,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Solution `{solution}` for Day {day} Part {part} borrows a `{expected}`, \
+                 but the parser of the day returns a `{found}`"
+            ),
+            Error::Parse {
+                day,
+                year,
+                parser,
+                source,
+            } => write!(
+                f,
+                "Parser `{parser}` for Day {day} of Year {year} failed: {source}"
+            ),
+            Error::UnknownVariant {
+                day,
+                part,
+                year,
+                variant: Some(variant),
+                available,
+            } => write!(
+                f,
+                "No variant `{variant}` of Day {day} Part {part} for Year {year} (available: {})",
+                available.join(", ")
+            ),
+            Error::UnknownVariant {
+                day,
+                part,
+                year,
+                variant: None,
+                available,
+            } => write!(
+                f,
+                "No default solution for Day {day} Part {part} of Year {year}, \
+                 choose a variant with --variant (available: {})",
+                available.join(", ")
+            ),
+            Error::VariantsDisagree {
+                day,
+                part,
+                year,
+                answers,
+            } => {
+                let answers: Vec<String> = answers
+                    .iter()
+                    .map(|(variant, answer)| format!("{variant} = {answer}"))
+                    .collect();
+                write!(
+                    f,
+                    "Variants of Day {day} Part {part} of Year {year} disagree: {}",
+                    answers.join(", ")
+                )
+            }
+            Error::Solution {
+                day,
+                part,
+                year,
+                solution,
+                source,
+            } => write!(
+                f,
+                "Solution `{solution}` for Day {day} Part {part} of Year {year} failed: {source}"
+            ),
+            Error::Panic {
+                day,
+                part,
+                year,
+                solution,
+                message,
+                location,
+            } => {
+                write!(
+                    f,
+                    "Solution `{solution}` for Day {day} Part {part} of Year {year} panicked"
+                )?;
+                if let Some(location) = location {
+                    write!(f, " at {location}")?;
+                }
+                write!(f, ": {message}")
+            }
+            Error::Timeout {
+                day,
+                part,
+                year,
+                solution,
+                budget,
+            } => write!(
+                f,
+                "Solution `{solution}` for Day {day} Part {part} of Year {year} did not finish \
+                 within {budget:?}"
+            ),
+            Error::AnswerMismatch {
+                day,
+                part,
+                year,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Day {day} Part {part} of Year {year} returned `{found}`, \
+                 but the recorded answer is `{expected}`"
+            ),
+            Error::ExamplesFailed { failed, total } => {
+                write!(f, "{failed} of {total} examples failed")
+            }
+            Error::InputUnavailable {
+                day,
+                year,
+                tried,
+                download,
+            } => {
+                let tried: Vec<String> = tried
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                write!(
+                    f,
+                    "No input available for Day {day} of Year {year}: not found in {}",
+                    tried.join(", ")
+                )?;
+                match download {
+                    Some(cause) => write!(f, ", and downloading it failed: {cause}"),
+                    None => write!(
+                        f,
+                        ", and remote input is disabled. Enable the `aoc-client` feature or \
+                         provide an input file (`-` for stdin)."
+                    ),
+                }
+            }
+            Error::InputRead { path, source } => {
+                write!(
+                    f,
+                    "Could not read the input from {}: {source}",
+                    path.display()
+                )
+            }
+            Error::PublishUnavailable => write!(
+                f,
+                "Publishing answers requires the `aoc-client` feature to be enabled."
+            ),
+            Error::ConfigMissing { key: "token" } => write!(
+                f,
+                "AOC session cookie is missing. \
+                 Please set it in the config file or AOC_TOKEN environment variable."
+            ),
+            Error::ConfigMissing { key } => {
+                write!(f, "`{key}` is missing from the config file.")
+            }
+            Error::ConfigParse { path, source } => {
+                write!(f, "Invalid config file {}: {source}", path.display())
+            }
+            Error::AnswersParse { path, source } => {
+                write!(f, "Invalid answers file {}: {source}", path.display())
+            }
+            Error::InvalidYear(year) => write!(f, "Invalid year `{year}`"),
+            Error::Network(e) => write!(f, "Could not communicate with Advent of Code: {e}"),
+            Error::SubmissionRejected {
+                day,
+                part,
+                year,
+                reason,
+            } => write!(
+                f,
+                "Answer for Day {day} Part {part} of Year {year} was rejected: {reason}"
+            ),
+            Error::KnownWrongAnswer {
+                day,
+                part,
+                year,
+                answer,
+                previous,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Answer `{answer}` for Day {day} Part {part} of Year {year} was not submitted: "
+                )?;
+                match reason {
+                    Rejection::TooHigh if previous != answer => {
+                        write!(f, "`{previous}` was already too high")
+                    }
+                    Rejection::TooLow if previous != answer => {
+                        write!(f, "`{previous}` was already too low")
+                    }
+                    reason => write!(f, "it was already rejected ({reason})"),
+                }
+            }
+            Error::SubmissionLocked {
+                day,
+                year,
+                remaining,
+            } => write!(
+                f,
+                "Day {day} of Year {year} does not accept answers for another {} \
+                 (use --wait to wait until then)",
+                crate::submissions::format_wait(*remaining)
+            ),
+            Error::SubmissionsParse { path, source } => {
+                write!(f, "Invalid submission history {}: {source}", path.display())
+            }
+            Error::FileExists(path) => write!(f, "{} already exists", path.display()),
+            Error::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Incorrect => write!(f, "that's not the right answer"),
+            Rejection::TooHigh => write!(f, "that's not the right answer, it is too high"),
+            Rejection::TooLow => write!(f, "that's not the right answer, it is too low"),
+            Rejection::TooRecent => write!(f, "you gave an answer too recently"),
+            Rejection::WrongLevel => write!(f, "this part is already solved or not unlocked yet"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Solution { source, .. } | Error::Parse { source, .. } => Some(source.as_ref()),
+            Error::ConfigParse { source, .. } | Error::AnswersParse { source, .. } => Some(source),
+            Error::Network(e) => Some(e.as_ref()),
+            Error::SubmissionsParse { source, .. } => Some(source),
+            Error::InputUnavailable {
+                download: Some(cause),
+                ..
+            } => Some(cause.as_ref()),
+            Error::InputRead { source, .. } => Some(source),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "aoc-client")]
+impl From<aoc_client::AocError> for Error {
+    fn from(e: aoc_client::AocError) -> Self {
+        Error::Network(Box::new(e))
+    }
+}
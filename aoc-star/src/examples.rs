@@ -0,0 +1,73 @@
+//! Checking the solutions against the examples given in their `#[star]`
+//! attribute (`test`), the same way the tests generated for `cargo test` do,
+//! but from the binary and with a summary of every example.
+
+use crate::panics;
+use crate::runner::solve_parsed_example;
+use crate::{AocEntry, SolutionResult, Solver};
+
+/// Outcome of running a solution on its example.
+#[derive(Debug)]
+pub struct ExampleRun {
+    /// The solution that was run.
+    pub entry: &'static AocEntry,
+    /// The answer returned on the example, or the error raised by the
+    /// solution (or its parser).
+    pub result: SolutionResult,
+}
+
+impl ExampleRun {
+    /// The answer the solution must return for its example.
+    pub fn expected(&self) -> &'static str {
+        self.entry
+            .example
+            .expect("only solutions with an example are run")
+            .expected
+    }
+
+    /// Whether the solution returned the expected answer.
+    pub fn passed(&self) -> bool {
+        self.result
+            .as_deref()
+            .is_ok_and(|answer| answer == self.expected())
+    }
+}
+
+/// Runs every solution that has an example, optionally restricted to `day`
+/// and to the solutions that apply to `year` (including the year-agnostic
+/// ones). The runs are sorted by year, day, part and variant.
+pub fn run_examples(day: Option<u32>, year: Option<i32>) -> Vec<ExampleRun> {
+    let mut entries: Vec<&'static AocEntry> = crate::inventory::iter::<AocEntry>
+        .into_iter()
+        .filter(|entry| entry.example.is_some())
+        .filter(|entry| day.is_none_or(|day| entry.day == day))
+        .filter(|entry| {
+            year.is_none_or(|year| entry.year.is_none_or(|entry_year| entry_year == year))
+        })
+        .collect();
+    entries.sort_by_key(|entry| (entry.year, entry.day, entry.part, entry.variant));
+
+    entries
+        .into_iter()
+        .map(|entry| ExampleRun {
+            entry,
+            result: solve_example(entry),
+        })
+        .collect()
+}
+
+/// Runs the solution on its example, parsing it first with the parser of the
+/// day if the solution borrows the parsed input.
+/// A panic is reported as an error, so that the other examples still run.
+fn solve_example(entry: &AocEntry) -> SolutionResult {
+    let example = entry
+        .example
+        .expect("only solutions with an example are run");
+    panics::catch(|| match entry.func {
+        Solver::Raw(func) => func(example.input.to_string()),
+        Solver::Parsed { func, .. } => {
+            solve_parsed_example(entry.day, entry.year, example.input, func)
+        }
+    })
+    .unwrap_or_else(|panic| Err(panic.into()))
+}
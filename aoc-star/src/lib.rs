@@ -31,7 +31,7 @@
 //!     input.lines().count().to_string()
 //! }
 //!
-//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! fn main() -> Result<(), aoc_star::Error> {
 //!     aoc_star::run()
 //! }
 //! ```
@@ -50,7 +50,8 @@
 //!
 //! - `aoc-client` (optional): enable remote input fetching and answer
 //!   submission using the [`aoc-client`](https://crates.io/crates/aoc-client)
-//!   crate. When disabled you must always provide an `--input-file`.
+//!   crate. When disabled, inputs are read from `--input-file` or from the
+//!   input cache.
 //! - `test-helpers`: re-exports some internals (`CommandArgument` and
 //!   `run_with_result`) to make integration testing easier.
 //!
@@ -58,49 +59,274 @@
 //!
 //! The `#[star(day = X, part = Y, year = Z)]` attribute macro registers
 //! the annotated function as the solution for the specified day, part, and
-//! optional year. The function must have the signature `fn(String) -> String`,
-//! where the input `String` contains the puzzle input, and the returned
-//! `String` is the answer.
+//! optional year. The function takes the puzzle input either as a `String` or
+//! as a `&str`, and returns the answer as any type implementing
+//! [`Display`](std::fmt::Display), e.g. `fn(&str) -> u64` or
+//! `fn(String) -> String`.
+//!
+//! Solutions may also be fallible and return a `Result<T, E>` where `T`
+//! implements `Display` and `E` converts into `Box<dyn Error + Send + Sync>`
+//! (for example `anyhow::Result<u64>`). Errors are reported by the runner
+//! together with the day and part that produced them instead of panicking.
+//!
+//! The example from the puzzle text can be attached to a solution, either
+//! inline with `example = "..."` or from a file (relative to the crate root)
+//! with `example_file = "..."`, together with the `expected` answer. A
+//! `#[test]` checking the solution against it is generated, so `cargo test`
+//! runs every example:
+//!
+//! ```no_run
+//! use aoc_star::star;
+//!
+//! #[star(day = 1, part = 1, example = "1abc2\npqr3stu8vwx", expected = "142")]
+//! fn day1_part1(input: &str) -> u32 {
+//!     // ...
+//!     # 142
+//! }
+//! ```
 //!
 //! If the `year` parameter is omitted, the solution is considered
 //! year-agnostic and will be used for any year that does not halve a more specific solution.
 //!
+//! Several implementations of the same day and part can be registered as
+//! named variants with `variant = "..."`. The unnamed one is run by default,
+//! the others with `--variant <name>`, and `--compare` runs all of them on the
+//! same input:
+//!
+//! ```no_run
+//! use aoc_star::star;
+//!
+//! #[star(day = 9, part = 2)]
+//! fn day9_part2(input: &str) -> usize {
+//!     input.lines().filter(|line| line.contains('x')).count()
+//! }
+//!
+//! #[star(day = 9, part = 2, variant = "fast")]
+//! fn day9_part2_fast(input: &str) -> usize {
+//!     input.lines().filter(|line| line.as_bytes().contains(&b'x')).count()
+//! }
+//! ```
+//!
+//! A solution can be given a time budget with `timeout = "30s"` (`ms`, `s`,
+//! `m` or `h`), replacing the one given with `--timeout`. A solution that
+//! does not finish in time fails with [`Error::Timeout`] and is left running
+//! in the background, so that a runaway brute force does not hang `--all`
+//! (see the `worker` module for what that costs).
+//!
+//! ### `star_parser` macro
+//!
+//! When both parts of a day start by parsing the input the same way, the
+//! parsing can be registered once with `#[star_parser(day = X, year = Z)]`.
+//! Solutions of that day then borrow the parsed value instead of taking the
+//! raw input. The runner parses the input once, shares the parsed value
+//! between the parts, and times parsing separately from solving:
+//!
+//! ```no_run
+//! use aoc_star::{star, star_parser};
+//!
+//! struct Grid(Vec<Vec<u8>>);
+//!
+//! #[star_parser(day = 7)]
+//! fn parse(input: &str) -> Grid {
+//!     Grid(input.lines().map(|line| line.bytes().collect()).collect())
+//! }
+//!
+//! #[star(day = 7, part = 1)]
+//! fn day7_part1(grid: &Grid) -> usize {
+//!     grid.0.len()
+//! }
+//! ```
+//!
+//! As with solutions, a parser without a `year` is used for every year that
+//! does not have a more specific one, and it may return a `Result`.
+//!
+//! ### Errors
+//!
+//! Every failure is reported as an [`Error`], whose variants describe the cause
+//! (no registered solution, missing input, invalid config, network issues,
+//! rejected submission, ...). [`Error::exit_code`] maps each cause to a
+//! distinct process exit code:
+//!
+//! ```no_run
+//! if let Err(e) = aoc_star::run() {
+//!     eprintln!("{e}");
+//!     std::process::exit(e.exit_code());
+//! }
+//! ```
+//!
+//! ### Running solutions from code
+//!
+//! [`run`] parses the command line and prints its results. To embed the
+//! runner in another program (a dashboard, a test harness, ...), use the
+//! [`Runner`] builder instead: it neither reads the command line nor writes
+//! to stdout, and returns a [`RunOutcome`] with the answer, the timings, the
+//! solution that was run and the year it was run for:
+//!
+//! ```no_run
+//! use aoc_star::{Input, Runner};
+//!
+//! let outcome = Runner::new()
+//!     .day(3)
+//!     .part(2)
+//!     .input(Input::Text("3   4\n4   3\n".to_string()))
+//!     .run()?;
+//! println!("{} = {}", outcome.entry.qualified_name(), outcome.answer);
+//! # Ok::<(), aoc_star::Error>(())
+//! ```
+//!
 //! ### CLI arguments
 //!
-//! The `run` function parses the following command line arguments:
+//! The `run` function parses a command line made of a subcommand and its
+//! arguments:
+//!
+//! - `run`: Run a solution and print its answer. This is the default command,
+//!   so `aoc-star -d 1 -p 2` is the same as `aoc-star run -d 1 -p 2`.
+//! - `bench`: Same as `run --bench`, with the number of runs given by
+//!   `--runs <N>` (10 by default).
+//! - `submit`: Same as `run --publish`.
+//! - `fetch --day <DAY>`: Download the input of a day into the input cache
+//!   (requires the `aoc-client` feature).
+//! - `new --day <DAY>`: Create the module of a new day from a template, with
+//!   a `#[star]` stub for each part (with commented-out `example` and
+//!   `expected` keys, for `test`), and declare it in
+//!   the `mod.rs` next to it (`--fetch` also downloads the input). The path
+//!   (`src/days/day{day:02}.rs` by default) and the template can be set in
+//!   the `new:` section of the config.
+//! - `test`: Run every solution that has an example on it (optionally only
+//!   those of `--day` and `--year`) and report the ones that do not return
+//!   the expected answer.
+//! - `list`: List the registered solutions grouped by year (or only those
+//!   that apply to `--year`), marking year-agnostic solutions, days with a
+//!   missing part and parts registered more than once. Each solution is shown
+//!   with its path, source location and the first line of its doc comment.
+//!   With `--markdown`, print a markdown table of the solutions linking each
+//!   of them to its source file, e.g. for a README.
+//! - `validate`: Check that no two solutions are registered for the same
+//!   day, part, year and variant, reporting the conflicting functions
+//!   otherwise.
+//! - `config`: Create the config file, prompting for the session cookie and
+//!   the default year (`--setup` is a deprecated alias).
+//!
+//! `run` accepts the following arguments:
 //!
 //! - `--day <DAY>`: The Advent of Code day (1–25).
-//! - `--part <PART>`: The puzzle part (usually 1 or 2).
+//! - `--part <PART>`: The puzzle part (usually 1 or 2), or `both` to run
+//!   both parts on the same input (parsed once) and print both answers. With
+//!   `--publish`, only the answer of the first part that is not solved yet
+//!   is submitted.
 //! - `--year <YEAR>`: The Advent of Code year (e.g., 2024). Defaults to
 //!   the current year if not provided.
-//! - `--input-file <FILE>`: Path to a file containing the puzzle input.
-//!   This argument is required if the `aoc-client` feature is not enabled.
+//! - `--input-file <FILE>`: Path to a file containing the puzzle input, or
+//!   `-` to read it from stdin. When omitted, the input is read from the
+//!   input cache, and downloaded into it first if the `aoc-client` feature is
+//!   enabled; [`Error::InputUnavailable`] lists the paths that were tried.
 //! - `--publish`: If provided and the `aoc-client` feature is enabled,
-//!   the computed answer will be submitted to Advent of Code.
+//!   the computed answer will be submitted to Advent of Code. Every
+//!   submission is logged in `answers/submissions.jsonl` (in `answers_dir`
+//!   if the config sets it), and an answer is
+//!   not submitted again if the part is already solved or if previous
+//!   submissions show that it is wrong ([`Error::KnownWrongAnswer`]).
+//! - `--wait`: With `--publish`, wait until Advent of Code accepts answers
+//!   for the puzzle again when a previous submission asked to wait, instead
+//!   of failing with [`Error::SubmissionLocked`].
+//! - `--variant <NAME>`: Run the named variant of the solution instead of the
+//!   default one.
+//! - `--compare`: Run every variant of the solution on the same input, print
+//!   their answers and timings (benchmarked with `--bench`) and check that
+//!   they agree.
+//! - `--all`: Run every solution registered for the year (`--year` or the
+//!   default one) in order and print a table with answers and timings, with
+//!   parsing times in their own column for days with a `#[star_parser]`.
+//!   A panicking solution is reported as [`Error::Panic`], with its message
+//!   and location, and the other days still run.
+//! - `--bench [N]`: Run the solution `N` times (10 by default) on the loaded
+//!   input and print the min/median/mean/max time, excluding input loading.
+//!   Combined with `--all`, prints these statistics for every day along with
+//!   a total for the year.
+//! - `--format <plain|json|tsv>`: Print the results as human readable text
+//!   (the default), or as one record per solution with its year, day, part,
+//!   variant, answer (or error), elapsed and parsing times in seconds, and
+//!   submission outcome: JSON objects one per line, or tab-separated values
+//!   after a header line.
+//! - `-j`, `--jobs <N>`: With `--all`, run up to `N` days at once, still
+//!   reporting them in day order. Add `--serial-timing` to only run one
+//!   solution at a time, for accurate timings, while the inputs are loaded
+//!   concurrently.
+//! - `--timeout <DURATION>`: Give up on solutions that do not finish within
+//!   `DURATION` (e.g. `500ms`, `5s`, `2m`) with [`Error::Timeout`], unless
+//!   they have their own `timeout`. With `--bench`, it applies to each run.
+//! - `--record`: Record the answers in `answers/{year}.yml`, the store of
+//!   known answers (answers accepted by Advent of Code are recorded there
+//!   anyway).
+//! - `--verify`: Check the answers against the recorded ones, failing with
+//!   [`Error::AnswerMismatch`] for every answer that differs. Combined with
+//!   `--all`, this reruns the whole year as a regression test. Answers are
+//!   kept per puzzle, not per variant: every variant is checked against the
+//!   answer of its puzzle.
+//!
+//! Every command also accepts `-q`, `--quiet` to only print warnings and
+//! errors, and `-v`, `--verbose` to print debug messages, those of the
+//! solutions included (`-vv` for trace messages, including those of the HTTP
+//! client). These messages always go
+//! to stderr, so that stdout only holds the answers.
+//!
+//! Running a solution with conflicting registrations fails with
+//! [`Error::ConflictingSolutions`], naming every candidate and its location,
+//! instead of silently picking one of them.
 //!
 //! The default year is either the one on the config file or the current year.
 //! The config contains the session cookie needed to fetch inputs and publish answers and
 //! the default year. If not present, the config file can be created by running
-//! this command with the `--setup` flag. It will be located at `$XDG_CONFIG_HOME/aoc-star/config.toml`
+//! the `config` command. It will be located at `$XDG_CONFIG_HOME/aoc-star/config.toml`
 //! which in linux systems usually resolves to `~/.config/aoc-star/config.toml`.
 //!
+//! Downloaded inputs are cached in `input/{day}_{year}.txt`, in the current
+//! directory. `inputs_dir` and `input_pattern` (e.g.
+//! `{year}/day{day:02}.txt`) in the config, or the `AOC_STAR_INPUT_DIR`
+//! environment variable, change where. Likewise, the known answers are kept
+//! in `answers`, unless `answers_dir` in the config or the
+//! `AOC_STAR_ANSWERS_DIR` environment variable say otherwise.
+//!
 //! ## License
 
+mod answers;
+mod bench;
 mod cli;
 mod config;
+mod error;
+mod examples;
+mod list;
+mod logger;
+mod output;
+mod panics;
+mod registry;
+mod report;
 mod runner;
+mod scaffold;
+mod submissions;
+mod worker;
 
-// Re-export the star macro so users can just `use aoc_star::star;`.
-pub use aoc_star_derive::star;
+pub use error::{Error, Rejection};
+pub use registry::Conflict;
+pub use runner::{Input, RunOutcome, Runner};
+
+// Re-export the macros so users can just `use aoc_star::star;`.
+pub use aoc_star_derive::{star, star_parser};
+
+use std::any::Any;
+use std::time::Duration;
 
-use clap::Parser;
 // This re-export is unfortunately necessary because
 // the macro expansions of the `aoc_star_derive::star` macro
 // need to access the `inventory` crate from the same namespace
 // as this crate. There may be a better way to handle this in the future.
 pub use inventory;
 
-use crate::runner::run_with_result;
+use crate::cli::{Command, CommandArgument, PartSelection};
+use crate::examples::run_examples;
+use crate::output::OutputFormat;
+use crate::runner::{compare_variants, run_all, run_both_parts};
+use crate::submissions::Submission;
 
 #[cfg(any(test, feature = "test-helpers"))]
 pub mod test_helpers {
@@ -110,12 +336,33 @@ pub mod test_helpers {
     //! enabled
     //!
     //! It re-exports:
-    //! - [`CommandArgument`](crate::cli::CommandArgument): the parsed CLI
-    //!   arguments structure.
-    //! - [`run_with_result`](crate::runner::run_with_result): the
-    //!   programmatic entry point used by [`run`].
-    pub use crate::cli::CommandArgument;
-    pub use crate::runner::run_with_result;
+    //! - [`CommandArgument`](crate::cli::CommandArgument) and
+    //!   [`OutputFormat`](crate::output::OutputFormat): the parsed CLI
+    //!   arguments structure and the formats `--format` accepts.
+    //! - [`run_with_result`](crate::runner::run_with_result): runs a solution
+    //!   like the `run` command, announcing it on stdout, and returns its
+    //!   answer (use [`Runner`](crate::Runner) to embed aoc-star instead).
+    //! - [`run_all`](crate::runner::run_all) and
+    //!   [`StarRun`](crate::runner::StarRun): the entry point used by `--all`
+    //!   and the outcome of each solution it runs.
+    //! - [`bench_with_result`](crate::runner::bench_with_result) and
+    //!   [`BenchStats`](crate::bench::BenchStats): the entry point used by
+    //!   `--bench` and the timings it reports.
+    //! - [`compare_variants`](crate::runner::compare_variants): the entry
+    //!   point used by `--compare`.
+    //! - [`run_both_parts`](crate::runner::run_both_parts) and
+    //!   [`PartSelection`](crate::cli::PartSelection): the entry point used by
+    //!   `--part both` and the values `--part` accepts.
+    //! - [`run_examples`](crate::examples::run_examples) and
+    //!   [`ExampleRun`](crate::examples::ExampleRun): the entry point used by
+    //!   the `test` command and the outcome of each example it checks.
+    pub use crate::bench::BenchStats;
+    pub use crate::cli::{CommandArgument, PartSelection};
+    pub use crate::examples::{ExampleRun, run_examples};
+    pub use crate::output::OutputFormat;
+    pub use crate::runner::{
+        StarRun, bench_with_result, compare_variants, run_all, run_both_parts, run_with_result,
+    };
 }
 
 /// A registered Advent of Code solution.
@@ -129,8 +376,18 @@ pub mod test_helpers {
 /// - `year`: The Advent of Code year; if `None`, the solution is considered
 ///   year-agnostic and will be used for any year that does not have a
 ///   more specific solution.
-/// - `func`: The solution function, which must take the puzzle input as a
-///   `String` and return the answer as a `String`.
+/// - `variant`: The name given with `variant = "..."`, if any. Several
+///   variants of the same day and part may be registered; the unnamed one is
+///   run unless another one is requested with `--variant`.
+/// - `func`: The solution function, which takes the puzzle input (either as a
+///   `String` or as parsed by the `#[star_parser]` of the day) and returns the
+///   answer as a `String`, or the error raised by a fallible solution. The
+///   `#[star]` macro generates this adapter around the annotated function.
+/// - `example`: The example given in the `#[star]` attribute, if any.
+/// - `name`, `module_path`, `file`, `line`, `doc`: The annotated function, where
+///   it is defined and its doc comment, used to report errors and list the
+///   registered solutions.
+#[derive(Debug)]
 pub struct AocEntry {
     /// Advent of Code day number (1–25).
     pub day: u32,
@@ -138,11 +395,137 @@ pub struct AocEntry {
     pub part: u32,
     /// Advent of Code year, or `None` for year-agnostic solutions.
     pub year: Option<i32>,
+    /// Name of this implementation, or `None` for the default one.
+    pub variant: Option<&'static str>,
     /// The solution function that processes the puzzle input and returns the answer.
-    pub func: fn(String) -> String,
+    pub func: Solver,
+    /// Example input from the puzzle text and its expected answer.
+    pub example: Option<Example>,
+    /// Time budget of the solution, given with `timeout` in its `#[star]`
+    /// attribute. It replaces the one given with `--timeout`.
+    pub timeout: Option<Duration>,
+    /// Name of the annotated function.
+    pub name: &'static str,
+    /// Module defining the annotated function, as given by [`module_path!`].
+    pub module_path: &'static str,
+    /// Source file defining the annotated function, as given by [`file!`].
+    pub file: &'static str,
+    /// Line of the annotated function in [`file`](AocEntry::file).
+    pub line: u32,
+    /// Doc comment of the annotated function, if any.
+    pub doc: Option<&'static str>,
+}
+
+impl AocEntry {
+    /// Path of the annotated function, e.g. `my_crate::day01::part1`.
+    pub fn qualified_name(&self) -> String {
+        format!("{}::{}", self.module_path, self.name)
+    }
+
+    /// First line of the doc comment, if any.
+    pub fn summary(&self) -> Option<&'static str> {
+        self.doc.and_then(|doc| doc.lines().next())
+    }
+}
+
+/// An example input from the puzzle text, along with its expected answer.
+///
+/// Given with `#[star(..., example = "...", expected = "...")]` or
+/// `#[star(..., example_file = "path", expected = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    /// The example input.
+    pub input: &'static str,
+    /// The answer the solution must return for [`input`](Example::input).
+    pub expected: &'static str,
+}
+
+/// What a solution returns: the answer, or the error raised by a fallible
+/// solution.
+pub type SolutionResult = Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+/// The adapter around a solution function generated by the `#[star]` macro.
+#[derive(Debug, Clone, Copy)]
+pub enum Solver {
+    /// A solution taking the raw puzzle input.
+    Raw(fn(String) -> SolutionResult),
+    /// A solution borrowing the input parsed by the `#[star_parser]` of its
+    /// day.
+    Parsed {
+        /// Runs the solution, or returns `None` if the parsed input is not of
+        /// the type the solution expects.
+        func: fn(&dyn Any) -> Option<SolutionResult>,
+        /// Name of the type the solution expects.
+        input_type: fn() -> &'static str,
+    },
+}
+
+/// A registered input parser, shared by the solutions of a day.
+///
+/// Instances of this type are created by the `#[star_parser]` attribute macro
+/// and collected via the [`inventory`] crate, like [`AocEntry`].
+#[derive(Debug)]
+pub struct AocParser {
+    /// Advent of Code day number (1–25).
+    pub day: u32,
+    /// Advent of Code year, or `None` for year-agnostic parsers.
+    pub year: Option<i32>,
+    /// The parser function, which takes the puzzle input and returns the
+    /// parsed input, or the error raised by a fallible parser.
+    pub func: fn(String) -> Result<ParsedInput, Box<dyn std::error::Error + Send + Sync>>,
+    /// Name of the annotated function.
+    pub name: &'static str,
+    /// Module defining the annotated function, as given by [`module_path!`].
+    pub module_path: &'static str,
+    /// Source file defining the annotated function, as given by [`file!`].
+    pub file: &'static str,
+    /// Line of the annotated function in [`file`](AocParser::file).
+    pub line: u32,
+}
+
+impl AocParser {
+    /// Path of the annotated function, e.g. `my_crate::day07::parse`.
+    pub fn qualified_name(&self) -> String {
+        format!("{}::{}", self.module_path, self.name)
+    }
+}
+
+/// The value returned by a `#[star_parser]`, along with the name of its type.
+#[derive(Debug)]
+pub struct ParsedInput {
+    value: Box<dyn Any>,
+    type_name: &'static str,
+}
+
+impl ParsedInput {
+    /// Wraps a parsed input.
+    pub fn new<T: Any>(value: T) -> Self {
+        ParsedInput {
+            value: Box::new(value),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// The parsed input.
+    pub fn value(&self) -> &dyn Any {
+        self.value.as_ref()
+    }
+
+    /// Name of the type of the parsed input.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
 }
 
 crate::inventory::collect!(AocEntry);
+crate::inventory::collect!(AocParser);
+
+#[doc(hidden)]
+pub mod __private {
+    //! Support code for the expansion of the macros. Not part of the public
+    //! API.
+    pub use crate::runner::solve_parsed_example;
+}
 
 /// Run the Advent of Code solution based on command line arguments.
 ///
@@ -151,34 +534,174 @@ crate::inventory::collect!(AocEntry);
 ///
 /// 1. Parses the command line using [`clap`].
 /// 2. Locates the registered solution for the requested day/part/year.
-/// 3. Loads the input (either from `--input-file` or, if the `aoc-client`
-///    feature is enabled, remotely from Advent of Code).
+/// 3. Loads the input (from `--input-file`, stdin, the input cache or, if
+///    the `aoc-client` feature is enabled, remotely from Advent of Code).
 /// 4. Optionally publishes the answer when `--publish` is used and
 ///    the `aoc-client` feature is enabled.
 /// 5. Prints the resulting answer to stdout.
 ///
+/// With `--all`, every solution registered for the year is run instead and a
+/// summary table with answers and timings is printed.
+///
+/// These are the arguments of the `run` command, which is used when no
+/// subcommand is given. The other commands are `bench` (same as `run
+/// --bench`), `submit` (same as `run --publish`), `fetch` (download the input
+/// of a day), `test` (check the solutions against their examples), `list`
+/// (list the registered solutions, as a markdown table with `--markdown`),
+/// `validate` (check that no two solutions are registered for the same day,
+/// part, year and variant) and `config` (create the config file).
+///
+/// With `--compare`, every variant of the solution is run on the same input
+/// and their answers and timings are compared.
+///
+/// With `--bench [N]`, the solution (or every solution, with `--all`) is run
+/// `N` times on the loaded input and timing statistics are printed. Input
+/// loading is not included in the timings.
+///
 /// # Errors
 ///
-/// Returns an error if input reading or (when enabled) communication with
-/// Advent of Code fails. If no matching solution is found, this function
-/// will panic.
+/// Returns an [`Error`] if no matching solution is registered, or if input
+/// reading, the solution itself or (when enabled) communication with Advent of
+/// Code fails. With `--all`, the first failure is returned after the summary
+/// has been printed; `test` returns [`Error::ExamplesFailed`] if an example
+/// was not solved.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// fn main() -> Result<(), aoc_star::Error> {
 ///     aoc_star::run()
 /// }
 /// ```
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+pub fn run() -> Result<(), Error> {
     // We get the command line arguments
-    let command_argument = cli::CommandArgument::parse();
-    if command_argument.setup {
-        config::setup_config_prompt()?;
-        println!("Configuration file created successfully.");
+    let cli = cli::Cli::parse_checked();
+    logger::init(cli.log_level());
+    match cli.into_command() {
+        Command::Run(command_argument) => run_command(command_argument),
+        Command::Bench(args) => run_command(args.into()),
+        Command::Submit(args) => run_command(args.into()),
+        Command::Fetch(args) => runner::fetch_input(args.day, args.year),
+        Command::New(args) => {
+            let year = runner::resolve_year(args.year)?;
+            scaffold::new_day(args.day, year)?;
+            if args.fetch {
+                runner::fetch_input(args.day, Some(year))?;
+            }
+            Ok(())
+        }
+        Command::Test(args) => {
+            let runs = run_examples(args.day, args.year);
+            report::print_examples(&runs);
+            let failed = runs.iter().filter(|run| !run.passed()).count();
+            if failed > 0 {
+                return Err(Error::ExamplesFailed {
+                    failed,
+                    total: runs.len(),
+                });
+            }
+            Ok(())
+        }
+        Command::List(args) => {
+            list::print_registry(args.year, args.markdown);
+            Ok(())
+        }
+        Command::Validate => {
+            let count = registry::validate()?;
+            println!("{count} solutions registered, no conflicts found.");
+            Ok(())
+        }
+        Command::Config => {
+            config::setup_config_prompt()?;
+            log::info!("Configuration file created successfully.");
+            Ok(())
+        }
+    }
+}
+
+/// Runs the solution(s) selected by the arguments of `run` (also used by
+/// `bench` and `submit`) and prints the outcome in the requested format.
+fn run_command(command_argument: CommandArgument) -> Result<(), Error> {
+    let format = command_argument.format;
+    let (record, verify) = (command_argument.record, command_argument.verify);
+    if command_argument.all {
+        let mut runs = run_all(command_argument)?;
+        check_answers(&mut runs, record, verify)?;
+        match format {
+            OutputFormat::Plain => report::print_summary(&runs),
+            format => output::print_records(format, &runs, None),
+        }
+        return first_error(runs);
+    }
+    if command_argument.compare {
+        let mut runs = compare_variants(command_argument)?;
+        check_answers(&mut runs, record, verify)?;
+        match format {
+            OutputFormat::Plain => report::print_comparison(&runs),
+            format => output::print_records(format, &runs, None),
+        }
+        let answer = runner::check_agreement(runs)?;
+        log::info!("All variants agree: {answer}");
         return Ok(());
     }
-    let result = run_with_result(command_argument)?;
-    println!("{result}");
+    if command_argument.part == PartSelection::Both {
+        let (publish, wait) = (command_argument.publish, command_argument.wait);
+        let mut runs = run_both_parts(command_argument)?;
+        check_answers(&mut runs, record, verify)?;
+        if format == OutputFormat::Plain {
+            report::print_parts(&runs);
+        }
+        let published = if publish {
+            runner::publish_first_unsolved(&runs, wait)
+        } else {
+            Ok(None)
+        };
+        output::print_records(format, &runs, Submission::of(&published));
+        published?;
+        return first_error(runs);
+    }
+
+    let (entry, mut run) = runner::run_requested(&command_argument)?;
+    check_answers(std::slice::from_mut(&mut run), record, verify)?;
+    if format == OutputFormat::Plain
+        && let Ok(answer) = &run.result
+    {
+        println!("{answer}");
+        if let Some(stats) = &run.bench {
+            report::print_bench(stats);
+        }
+    }
+    let published = match &run.result {
+        Ok(answer) if command_argument.publish => {
+            runner::publish(entry, run.year, answer, command_argument.wait)
+        }
+        _ => Ok(None),
+    };
+    output::print_records(
+        format,
+        std::slice::from_ref(&run),
+        Submission::of(&published),
+    );
+    published?;
+    run.result.map(drop)
+}
+
+/// Records the answers of the runs (`--record`), or checks them against the
+/// recorded ones (`--verify`).
+fn check_answers(runs: &mut [runner::StarRun], record: bool, verify: bool) -> Result<(), Error> {
+    if verify {
+        answers::verify_runs(runs)?;
+    }
+    if record {
+        answers::record_runs(runs)?;
+    }
     Ok(())
 }
+
+/// The first error among the runs of a batch, if any.
+fn first_error(runs: Vec<runner::StarRun>) -> Result<(), Error> {
+    match runs.into_iter().find_map(|run| run.result.err()) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
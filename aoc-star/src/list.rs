@@ -0,0 +1,299 @@
+//! Listing of the solution registry (`list`).
+//! Solutions are grouped by year, year-agnostic ones last, and each day
+//! notes the parts that are missing or registered more than once. Every
+//! solution is then described by its path, source location and the first
+//! line of its doc comment.
+//! With `list --markdown`, the registry is printed as a table linking each
+//! solution to its source file, ready to be pasted in a README.
+
+use std::collections::BTreeMap;
+
+use crate::AocEntry;
+
+/// Registered solutions of each part, by day, for a single year.
+type Days<'a> = BTreeMap<u32, BTreeMap<u32, Vec<&'a AocEntry>>>;
+
+/// Prints every registered solution, optionally restricted to `year` (and the
+/// year-agnostic solutions).
+pub(crate) fn print_registry(year: Option<i32>, markdown: bool) {
+    let entries: Vec<&AocEntry> = crate::inventory::iter::<AocEntry>
+        .into_iter()
+        .filter(|entry| year.is_none() || entry.year.is_none() || entry.year == year)
+        .collect();
+
+    let lines = if markdown {
+        markdown_table(&entries)
+    } else {
+        listing(&entries)
+    };
+    for line in lines {
+        println!("{line}");
+    }
+}
+
+/// Groups `entries` by year, with the year-agnostic ones last.
+fn group<'a>(entries: &[&'a AocEntry]) -> Vec<(Option<i32>, Days<'a>)> {
+    let mut years: BTreeMap<i32, Days> = BTreeMap::new();
+    let mut agnostic = Days::new();
+    for entry in entries {
+        let days = match entry.year {
+            Some(year) => years.entry(year).or_default(),
+            None => &mut agnostic,
+        };
+        days.entry(entry.day)
+            .or_default()
+            .entry(entry.part)
+            .or_default()
+            .push(*entry);
+    }
+
+    let mut groups: Vec<(Option<i32>, Days)> = years
+        .into_iter()
+        .map(|(year, days)| (Some(year), days))
+        .collect();
+    if !agnostic.is_empty() {
+        groups.push((None, agnostic));
+    }
+    groups
+}
+
+/// The lines of the listing of `entries`.
+fn listing(entries: &[&AocEntry]) -> Vec<String> {
+    if entries.is_empty() {
+        return vec!["No solutions registered.".to_string()];
+    }
+
+    let mut lines = Vec::new();
+    for (year, days) in group(entries) {
+        lines.push(match year {
+            Some(year) => format!("Year {year}"),
+            None => "Year-agnostic".to_string(),
+        });
+        for (day, parts) in &days {
+            lines.push(describe_day(*day, parts));
+            lines.extend(parts.values().flatten().map(|entry| describe_entry(entry)));
+        }
+    }
+    lines
+}
+
+/// Describes the parts registered for a day, e.g.
+/// `  Day  2: part 1 (missing part 2)`.
+fn describe_day(day: u32, parts: &BTreeMap<u32, Vec<&AocEntry>>) -> String {
+    let registered: Vec<String> = parts.keys().map(|part| format!("part {part}")).collect();
+
+    // Day 25 only has one part
+    let expected_parts: &[u32] = if day == 25 { &[1] } else { &[1, 2] };
+    let mut notes: Vec<String> = expected_parts
+        .iter()
+        .filter(|part| !parts.contains_key(part))
+        .map(|part| format!("missing part {part}"))
+        .collect();
+    for (part, entries) in parts {
+        let mut variants: BTreeMap<Option<&str>, usize> = BTreeMap::new();
+        for entry in entries {
+            *variants.entry(entry.variant).or_default() += 1;
+        }
+        notes.extend(variants.into_iter().filter(|(_, count)| *count > 1).map(
+            |(variant, count)| match variant {
+                Some(variant) => {
+                    format!("part {part} variant `{variant}` registered {count} times")
+                }
+                None => format!("part {part} registered {count} times"),
+            },
+        ));
+    }
+
+    let mut line = format!("  Day {day:>2}: {}", registered.join(", "));
+    if !notes.is_empty() {
+        line.push_str(&format!(" ({})", notes.join("; ")));
+    }
+    line
+}
+
+/// Describes a single solution, e.g.
+/// `    part 1: aoc::day01::part1 (src/day01.rs:5) - Sum of the calibration values`
+/// or `    part 2 [fast]: aoc::day01::part2_fast (src/day01.rs:12)`.
+fn describe_entry(entry: &AocEntry) -> String {
+    let variant = entry
+        .variant
+        .map(|variant| format!(" [{variant}]"))
+        .unwrap_or_default();
+    let mut line = format!(
+        "    part {}{variant}: {} ({}:{})",
+        entry.part,
+        entry.qualified_name(),
+        entry.file,
+        entry.line
+    );
+    if let Some(summary) = entry.summary() {
+        line.push_str(&format!(" - {summary}"));
+    }
+    line
+}
+
+/// The lines of a markdown table of `entries`, linking every solution to the
+/// line of its source file.
+fn markdown_table(entries: &[&AocEntry]) -> Vec<String> {
+    let mut lines = vec![
+        "| Year | Day | Part | Solution | Description |".to_string(),
+        "| ---- | --: | ---: | -------- | ----------- |".to_string(),
+    ];
+    for (year, days) in group(entries) {
+        let year = year.map_or_else(|| "any".to_string(), |year| year.to_string());
+        for entry in days.values().flat_map(|parts| parts.values().flatten()) {
+            let variant = entry
+                .variant
+                .map(|variant| format!(" ({variant})"))
+                .unwrap_or_default();
+            lines.push(format!(
+                "| {year} | {} | {} | [`{}`]({}#L{}){variant} | {} |",
+                entry.day,
+                entry.part,
+                entry.name,
+                entry.file.replace('\\', "/"),
+                entry.line,
+                entry.summary().unwrap_or_default().replace('|', "\\|")
+            ));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AocEntry, Solver};
+
+    use super::{listing, markdown_table};
+
+    fn entry(day: u32, part: u32, year: Option<i32>) -> AocEntry {
+        AocEntry {
+            day,
+            part,
+            year,
+            variant: None,
+            func: Solver::Raw(Ok),
+            example: None,
+            timeout: None,
+            name: "solution",
+            module_path: "aoc::days",
+            file: "src/days.rs",
+            line: day * 10 + part,
+            doc: None,
+        }
+    }
+
+    #[test]
+    fn groups_entries_by_year_with_agnostic_last() {
+        let entries = [
+            entry(1, 1, None),
+            entry(2, 1, Some(2024)),
+            entry(2, 2, Some(2024)),
+            entry(1, 1, Some(2023)),
+            entry(1, 2, Some(2023)),
+        ];
+        let entries: Vec<&AocEntry> = entries.iter().collect();
+
+        assert_eq!(
+            listing(&entries),
+            [
+                "Year 2023",
+                "  Day  1: part 1, part 2",
+                "    part 1: aoc::days::solution (src/days.rs:11)",
+                "    part 2: aoc::days::solution (src/days.rs:12)",
+                "Year 2024",
+                "  Day  2: part 1, part 2",
+                "    part 1: aoc::days::solution (src/days.rs:21)",
+                "    part 2: aoc::days::solution (src/days.rs:22)",
+                "Year-agnostic",
+                "  Day  1: part 1 (missing part 2)",
+                "    part 1: aoc::days::solution (src/days.rs:11)",
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_missing_parts_and_duplicates() {
+        let entries = [
+            entry(3, 2, Some(2024)),
+            entry(3, 2, Some(2024)),
+            entry(25, 1, Some(2024)),
+            entry(4, 1, Some(2024)),
+        ];
+        let entries: Vec<&AocEntry> = entries.iter().collect();
+
+        let lines = listing(&entries);
+        let days: Vec<&str> = lines
+            .iter()
+            .map(String::as_str)
+            .filter(|line| !line.starts_with("    "))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                "Year 2024",
+                "  Day  3: part 2 (missing part 1; part 2 registered 2 times)",
+                "  Day  4: part 1 (missing part 2)",
+                "  Day 25: part 1",
+            ]
+        );
+        assert_eq!(lines.len(), days.len() + entries.len());
+    }
+
+    #[test]
+    fn describes_solutions_with_their_doc_summary() {
+        let entries = [AocEntry {
+            doc: Some("Sum of the calibration values.\n\nSee the puzzle for details."),
+            ..entry(1, 1, Some(2023))
+        }];
+        let entries: Vec<&AocEntry> = entries.iter().collect();
+
+        assert_eq!(
+            listing(&entries)[2],
+            "    part 1: aoc::days::solution (src/days.rs:11) - Sum of the calibration values."
+        );
+        assert_eq!(
+            markdown_table(&entries),
+            [
+                "| Year | Day | Part | Solution | Description |",
+                "| ---- | --: | ---: | -------- | ----------- |",
+                "| 2023 | 1 | 1 | [`solution`](src/days.rs#L11) | Sum of the calibration values. |",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_variants_separately_from_duplicates() {
+        let entries = [
+            entry(9, 2, Some(2024)),
+            AocEntry {
+                variant: Some("fast"),
+                ..entry(9, 2, Some(2024))
+            },
+            AocEntry {
+                variant: Some("fast"),
+                ..entry(9, 2, Some(2024))
+            },
+        ];
+        let entries: Vec<&AocEntry> = entries.iter().collect();
+
+        let lines = listing(&entries);
+        assert_eq!(
+            lines[1],
+            "  Day  9: part 2 (missing part 1; part 2 variant `fast` registered 2 times)"
+        );
+        assert_eq!(
+            lines[3],
+            "    part 2 [fast]: aoc::days::solution (src/days.rs:92)"
+        );
+        assert_eq!(
+            markdown_table(&entries)[3],
+            "| 2024 | 9 | 2 | [`solution`](src/days.rs#L92) (fast) |  |"
+        );
+    }
+
+    #[test]
+    fn reports_empty_registry() {
+        assert_eq!(listing(&[]), ["No solutions registered."]);
+    }
+}
@@ -0,0 +1,95 @@
+//! Logger printing the progress messages of the runner (which solution runs,
+//! where the input comes from, submissions, ...) to stderr, so that stdout
+//! only holds the results: answers, tables or records.
+//! The level is chosen on the command line: warnings and errors only with
+//! `-q`, informational messages by default, and more details with `-v`
+//! (debug) or `-vv` (trace, which also shows the messages of the HTTP stack).
+//! The messages of the solutions (`log::debug!` in a day) are shown at the
+//! same level as those of the runner.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Crates of the HTTP stack, whose messages are only shown with `-vv`.
+const QUIET_CRATES: &[&str] = &[
+    "cookie_store",
+    "h2",
+    "hyper",
+    "mio",
+    "native_tls",
+    "reqwest",
+    "rustls",
+    "tokio",
+    "tokio_util",
+    "want",
+];
+
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        shown(metadata.level(), metadata.target(), log::max_level())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            Level::Info => eprintln!("{}", record.args()),
+            level => eprintln!("[{}] {}", level.as_str().to_lowercase(), record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Whether a message of `level` from `target` is shown at `max_level`: the
+/// messages of the HTTP stack only are at the trace level.
+fn shown(level: Level, target: &str, max_level: LevelFilter) -> bool {
+    let krate = target.split("::").next().unwrap_or(target);
+    level <= max_level && (max_level == LevelFilter::Trace || !QUIET_CRATES.contains(&krate))
+}
+
+/// Installs the logger with the given maximum level. Does nothing if another
+/// logger is already installed, e.g. by the program embedding aoc-star.
+pub(crate) fn init(level: LevelFilter) {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::{Level, LevelFilter};
+
+    use super::shown;
+
+    #[test]
+    fn shows_the_messages_of_the_solutions() {
+        assert!(shown(Level::Debug, "aoc_star::runner", LevelFilter::Debug));
+        assert!(shown(
+            Level::Debug,
+            "my_solutions::day03",
+            LevelFilter::Debug
+        ));
+        assert!(!shown(
+            Level::Debug,
+            "my_solutions::day03",
+            LevelFilter::Info
+        ));
+
+        assert!(!shown(
+            Level::Debug,
+            "hyper::client::pool",
+            LevelFilter::Debug
+        ));
+        assert!(!shown(Level::Info, "reqwest", LevelFilter::Debug));
+        assert!(shown(
+            Level::Debug,
+            "hyper::client::pool",
+            LevelFilter::Trace
+        ));
+    }
+}
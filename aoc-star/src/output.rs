@@ -0,0 +1,179 @@
+//! Machine readable output of the runner (`--format json` and `--format tsv`).
+//! Every solution that was run is printed as a record with its year, day,
+//! part, variant, answer (or error), elapsed time and, when its answer was
+//! submitted, the outcome of the submission. JSON records are printed one per
+//! line (JSON Lines); TSV records follow a header line.
+//! Progress messages go to stderr (see the `logger` module), so that stdout
+//! only holds the records.
+
+use crate::runner::StarRun;
+use crate::submissions::Submission;
+
+/// How the runner prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable answers and tables
+    #[default]
+    Plain,
+    /// One JSON object per solution, per line
+    Json,
+    /// Tab-separated values, after a header line
+    Tsv,
+}
+
+/// A solution that was run, as printed by the machine readable formats.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Record<'a> {
+    year: i32,
+    day: u32,
+    part: u32,
+    variant: Option<&'a str>,
+    answer: Option<&'a str>,
+    error: Option<String>,
+    /// Seconds spent in the solution (the mean, when benchmarking).
+    elapsed: f64,
+    /// Seconds spent parsing the input, on the run that parsed it.
+    parse: Option<f64>,
+    submission: Option<&'static str>,
+}
+
+const TSV_HEADER: [&str; 9] = [
+    "year",
+    "day",
+    "part",
+    "variant",
+    "answer",
+    "error",
+    "elapsed",
+    "parse",
+    "submission",
+];
+
+impl<'a> Record<'a> {
+    fn new(run: &'a StarRun, submission: Option<(u32, Submission)>) -> Self {
+        Record {
+            year: run.year,
+            day: run.day,
+            part: run.part,
+            variant: run.variant,
+            answer: run.result.as_deref().ok(),
+            error: run.result.as_ref().err().map(ToString::to_string),
+            elapsed: run.duration.as_secs_f64(),
+            parse: run.parse.map(|parse| parse.as_secs_f64()),
+            submission: submission
+                .filter(|(part, _)| *part == run.part)
+                .map(|(_, submission)| submission.as_str()),
+        }
+    }
+
+    fn to_tsv(&self) -> String {
+        let cells = [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.variant.map(tsv_escape).unwrap_or_default(),
+            self.answer.map(tsv_escape).unwrap_or_default(),
+            self.error.as_deref().map(tsv_escape).unwrap_or_default(),
+            self.elapsed.to_string(),
+            self.parse
+                .map(|parse| parse.to_string())
+                .unwrap_or_default(),
+            self.submission.unwrap_or_default().to_string(),
+        ];
+        cells.join("\t")
+    }
+}
+
+/// Escapes the characters that would break a TSV row.
+fn tsv_escape(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Prints a record for each run, in the given machine readable format.
+/// `submission` is the part whose answer was submitted and the outcome, if
+/// any. Nothing is printed for [`OutputFormat::Plain`], whose output depends
+/// on the command (see the `report` module).
+pub(crate) fn print_records(
+    format: OutputFormat,
+    runs: &[StarRun],
+    submission: Option<(u32, Submission)>,
+) {
+    let records = runs.iter().map(|run| Record::new(run, submission));
+    match format {
+        OutputFormat::Plain => {}
+        OutputFormat::Json => {
+            for record in records {
+                let json = serde_json::to_string(&record).expect("records are valid JSON");
+                println!("{json}");
+            }
+        }
+        OutputFormat::Tsv => {
+            println!("{}", TSV_HEADER.join("\t"));
+            for record in records {
+                println!("{}", record.to_tsv());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Error, Rejection};
+
+    fn run(part: u32, result: Result<&str, Error>) -> StarRun {
+        StarRun {
+            day: 3,
+            part,
+            year: 2024,
+            variant: None,
+            result: result.map(str::to_string),
+            duration: Duration::from_millis(1500),
+            parse: None,
+            bench: None,
+        }
+    }
+
+    #[test]
+    fn serializes_records_as_json() {
+        let run = run(2, Ok("42"));
+        let submission = Some((2, Submission::Correct));
+
+        let json = serde_json::to_string(&Record::new(&run, submission)).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"year":2024,"day":3,"part":2,"variant":null,"answer":"42","error":null,"elapsed":1.5,"parse":null,"submission":"correct"}"#
+        );
+    }
+
+    #[test]
+    fn formats_records_as_tsv() {
+        let error = Error::NoSolution {
+            day: 3,
+            part: 1,
+            year: 2024,
+        };
+        let failed = run(1, Err(error));
+        let record = Record::new(&failed, Some((2, Submission::Correct)));
+        assert_eq!(
+            record.to_tsv(),
+            "2024\t3\t1\t\t\tNo solution found for Day 3 Part 1 of Year 2024\t1.5\t\t"
+        );
+
+        let multiline = run(2, Ok("#.#\n.#.\tx"));
+        let record = Record::new(
+            &multiline,
+            Some((2, Submission::Rejected(Rejection::TooRecent))),
+        );
+        assert_eq!(
+            record.to_tsv(),
+            "2024\t3\t2\t\t#.#\\n.#.\\tx\t\t1.5\t\ttoo-recent"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Catching the panics of solutions, so that a panicking solution is reported
+//! as failed, with its panic message and location, instead of aborting the
+//! rest of the batch.
+//! While a panic is caught, the panic hook records its location instead of
+//! printing it; the backtrace (with `RUST_BACKTRACE=1`) is logged at debug
+//! level. Panics elsewhere go to the previous hook as usual.
+
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use log::debug;
+
+thread_local! {
+    /// Whether the current thread is running a solution whose panics are
+    /// caught.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Location of the last panic caught on the current thread.
+    static LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// A caught panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Panic {
+    pub(crate) message: String,
+    /// Where the panic happened, e.g. `src/day03.rs:12:5`.
+    pub(crate) location: Option<String>,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Panic {}
+
+/// Runs `f`, returning the panic it raised, if any.
+pub(crate) fn catch<R>(f: impl FnOnce() -> R) -> Result<R, Panic> {
+    INSTALL_HOOK.call_once(install_hook);
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|payload| Panic {
+        message: message(payload.as_ref()),
+        location: LOCATION.take(),
+    })
+}
+
+/// Installs a panic hook recording the location of the caught panics, and
+/// deferring to the previous hook for the others.
+fn install_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CATCHING.get() {
+            return previous(info);
+        }
+
+        LOCATION.set(info.location().map(ToString::to_string));
+        let backtrace = Backtrace::capture();
+        if backtrace.status() == BacktraceStatus::Captured {
+            debug!("Backtrace of the panic:\n{backtrace}");
+        }
+    }));
+}
+
+/// The message of a panic, given its payload.
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Panic, catch};
+
+    #[test]
+    fn catches_panics_with_their_location() {
+        assert_eq!(catch(|| 42), Ok(42));
+
+        // Both the panic and the expected location point at the call site of
+        // the macro
+        macro_rules! panic_here {
+            () => {
+                (
+                    catch(|| panic!("bad {}", "input")),
+                    format!("{}:{}:{}", file!(), line!(), column!()),
+                )
+            };
+        }
+        let (panic, location) = panic_here!();
+        let panic = panic.unwrap_err();
+        assert_eq!(panic.message, "bad input");
+        assert_eq!(panic.location, Some(location));
+
+        let panic = catch(|| std::panic::panic_any(7)).unwrap_err();
+        assert_eq!(panic.message, "Box<dyn Any>");
+    }
+
+    #[test]
+    fn describes_panics() {
+        let panic = Panic {
+            message: "index out of bounds".to_string(),
+            location: Some("src/day03.rs:12:5".to_string()),
+        };
+        assert_eq!(
+            panic.to_string(),
+            "panicked at src/day03.rs:12:5: index out of bounds"
+        );
+        let panic = Panic {
+            location: None,
+            ..panic
+        };
+        assert_eq!(panic.to_string(), "panicked: index out of bounds");
+    }
+}
@@ -0,0 +1,436 @@
+//! Lookup of solutions in the registry filled by the `#[star]` macro.
+//! Year-specific solutions take precedence over year-agnostic ones, and
+//! several solutions registered for the same day, part, year and variant are
+//! a conflict rather than silently picking one of them. The same goes for the
+//! parsers registered with `#[star_parser]`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{AocEntry, AocParser, Error};
+
+/// Several solutions registered for the same day, part, year and variant.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub day: u32,
+    pub part: u32,
+    /// The year the solutions are registered for, `None` if they are
+    /// year-agnostic.
+    pub year: Option<i32>,
+    /// The variant the solutions are registered as, `None` for the default
+    /// one.
+    pub variant: Option<&'static str>,
+    /// The conflicting solutions.
+    pub entries: Vec<&'static AocEntry>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "Day {} Part {} of Year {year}", self.day, self.part)?,
+            None => write!(f, "Day {} Part {} (year-agnostic)", self.day, self.part)?,
+        }
+        if let Some(variant) = self.variant {
+            write!(f, ", variant `{variant}`")?;
+        }
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "`{}` ({}:{})",
+                    entry.qualified_name(),
+                    entry.file,
+                    entry.line
+                )
+            })
+            .collect();
+        write!(f, ": {}", entries.join(", "))
+    }
+}
+
+/// Every registered solution.
+fn registered() -> Vec<&'static AocEntry> {
+    crate::inventory::iter::<AocEntry>.into_iter().collect()
+}
+
+/// Looks for the solution of the given day, part and variant (the default one
+/// if `None`) in the inventory.
+/// If no specific year match is found, we fall back to year-agnostic solutions.
+pub(crate) fn find_entry(
+    day: u32,
+    part: u32,
+    year: i32,
+    variant: Option<&str>,
+) -> Result<&'static AocEntry, Error> {
+    find_in(&registered(), day, part, year, variant)
+}
+
+fn find_in(
+    entries: &[&'static AocEntry],
+    day: u32,
+    part: u32,
+    year: i32,
+    variant: Option<&str>,
+) -> Result<&'static AocEntry, Error> {
+    let variants = variants_in(entries, day, part, year);
+
+    let (candidates_year, candidates) = [Some(year), None]
+        .into_iter()
+        .map(|candidates_year| {
+            let candidates: Vec<&'static AocEntry> = variants
+                .iter()
+                .copied()
+                .filter(|e| e.year == candidates_year && e.variant == variant)
+                .collect();
+            (candidates_year, candidates)
+        })
+        .find(|(_, candidates)| !candidates.is_empty())
+        .unwrap_or_default();
+
+    match candidates.len() {
+        0 if variants.is_empty() => Err(Error::NoSolution { day, part, year }),
+        0 => {
+            let mut available: Vec<String> = variants
+                .iter()
+                .map(|e| e.variant.unwrap_or("default").to_string())
+                .collect();
+            available.sort_unstable();
+            available.dedup();
+            Err(Error::UnknownVariant {
+                day,
+                part,
+                year,
+                variant: variant.map(str::to_string),
+                available,
+            })
+        }
+        1 => Ok(candidates[0]),
+        _ => Err(Error::ConflictingSolutions(vec![Conflict {
+            day,
+            part,
+            year: candidates_year,
+            variant: candidates[0].variant,
+            entries: candidates,
+        }])),
+    }
+}
+
+/// Every variant of the given day and part that applies to `year`, sorted by
+/// name with the default one first.
+///
+/// As with [`find_entry`], year-specific solutions hide year-agnostic ones of
+/// the same variant.
+pub(crate) fn find_variants(day: u32, part: u32, year: i32) -> Vec<&'static AocEntry> {
+    variants_in(&registered(), day, part, year)
+}
+
+fn variants_in(
+    entries: &[&'static AocEntry],
+    day: u32,
+    part: u32,
+    year: i32,
+) -> Vec<&'static AocEntry> {
+    let mut variants: Vec<&'static AocEntry> = entries
+        .iter()
+        .copied()
+        .filter(|e| e.day == day && e.part == part)
+        .filter(|e| match e.year {
+            Some(e_year) => e_year == year,
+            None => !entries.iter().any(|other| {
+                other.day == day
+                    && other.part == part
+                    && other.year == Some(year)
+                    && other.variant == e.variant
+            }),
+        })
+        .collect();
+    variants.sort_by_key(|e| e.variant);
+    variants
+}
+
+/// Looks for the parser of the given day in the inventory, falling back to a
+/// year-agnostic parser if none is registered for `year`.
+pub(crate) fn find_parser(day: u32, year: Option<i32>) -> Result<&'static AocParser, Error> {
+    let parsers: Vec<&'static AocParser> =
+        crate::inventory::iter::<AocParser>.into_iter().collect();
+    find_parser_in(&parsers, day, year)
+}
+
+fn find_parser_in(
+    parsers: &[&'static AocParser],
+    day: u32,
+    year: Option<i32>,
+) -> Result<&'static AocParser, Error> {
+    let (candidates_year, candidates) = [year, None]
+        .into_iter()
+        .map(|candidates_year| {
+            let candidates: Vec<&'static AocParser> = parsers
+                .iter()
+                .copied()
+                .filter(|p| p.day == day && p.year == candidates_year)
+                .collect();
+            (candidates_year, candidates)
+        })
+        .find(|(_, candidates)| !candidates.is_empty())
+        .unwrap_or_default();
+
+    match candidates.len() {
+        0 => Err(Error::NoParser { day, year }),
+        1 => Ok(candidates[0]),
+        _ => Err(Error::ConflictingParsers {
+            day,
+            year: candidates_year,
+            parsers: candidates,
+        }),
+    }
+}
+
+/// Every day and part with a default (unnamed) solution that applies to
+/// `year`, sorted. Parts that only have named variants are left out, since
+/// there is no solution to pick for them without a variant.
+pub(crate) fn stars_for_year(year: i32) -> Vec<(u32, u32)> {
+    stars_in(&registered(), year)
+}
+
+fn stars_in(entries: &[&'static AocEntry], year: i32) -> Vec<(u32, u32)> {
+    let mut stars: Vec<(u32, u32)> = entries
+        .iter()
+        .filter(|e| e.year.is_none_or(|e_year| e_year == year) && e.variant.is_none())
+        .map(|e| (e.day, e.part))
+        .collect();
+    stars.sort_unstable();
+    stars.dedup();
+    stars
+}
+
+/// Checks the whole registry for conflicting solutions and parsers
+/// (`validate`).
+///
+/// Returns the number of registered solutions if there is no conflict.
+pub(crate) fn validate() -> Result<usize, Error> {
+    let entries = registered();
+    let conflicts = conflicts_in(&entries);
+    if !conflicts.is_empty() {
+        return Err(Error::ConflictingSolutions(conflicts));
+    }
+
+    let mut parsers: BTreeMap<(Option<i32>, u32), Vec<&'static AocParser>> = BTreeMap::new();
+    for parser in crate::inventory::iter::<AocParser> {
+        parsers
+            .entry((parser.year, parser.day))
+            .or_default()
+            .push(parser);
+    }
+    if let Some(((year, day), parsers)) = parsers.into_iter().find(|(_, p)| p.len() > 1) {
+        return Err(Error::ConflictingParsers { day, year, parsers });
+    }
+
+    Ok(entries.len())
+}
+
+fn conflicts_in(entries: &[&'static AocEntry]) -> Vec<Conflict> {
+    type Key = (Option<i32>, u32, u32, Option<&'static str>);
+    let mut registrations: BTreeMap<Key, Vec<&'static AocEntry>> = BTreeMap::new();
+    for entry in entries {
+        registrations
+            .entry((entry.year, entry.day, entry.part, entry.variant))
+            .or_default()
+            .push(*entry);
+    }
+
+    registrations
+        .into_iter()
+        .filter(|(_, entries)| entries.len() > 1)
+        .map(|((year, day, part, variant), entries)| Conflict {
+            day,
+            part,
+            year,
+            variant,
+            entries,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AocEntry, AocParser, Error, ParsedInput, Solver};
+
+    use super::{conflicts_in, find_in, find_parser_in, stars_in, variants_in};
+
+    static ENTRIES: [AocEntry; 10] = [
+        entry("agnostic", 1, None, 10),
+        entry("specific", 1, Some(2024), 20),
+        entry("first", 2, Some(2024), 30),
+        entry("second", 2, Some(2024), 40),
+        entry("other_year", 2, Some(2023), 50),
+        entry("naive", 3, None, 60),
+        variant("fast", 3, None, "fast", 70),
+        variant("faster", 3, Some(2024), "fast", 80),
+        variant("fast_a", 4, None, "fast", 90),
+        variant("fast_b", 4, None, "fast", 100),
+    ];
+
+    const fn entry(name: &'static str, day: u32, year: Option<i32>, line: u32) -> AocEntry {
+        AocEntry {
+            day,
+            part: 1,
+            year,
+            variant: None,
+            func: Solver::Raw(Ok),
+            example: None,
+            timeout: None,
+            name,
+            module_path: "aoc::days",
+            file: "src/days.rs",
+            line,
+            doc: None,
+        }
+    }
+
+    const fn variant(
+        name: &'static str,
+        day: u32,
+        year: Option<i32>,
+        variant: &'static str,
+        line: u32,
+    ) -> AocEntry {
+        AocEntry {
+            variant: Some(variant),
+            ..entry(name, day, year, line)
+        }
+    }
+
+    static PARSERS: [AocParser; 4] = [
+        parser("parse", 7, None),
+        parser("parse_2024", 7, Some(2024)),
+        parser("first", 8, Some(2024)),
+        parser("second", 8, Some(2024)),
+    ];
+
+    const fn parser(name: &'static str, day: u32, year: Option<i32>) -> AocParser {
+        AocParser {
+            day,
+            year,
+            func: |input| Ok(ParsedInput::new(input)),
+            name,
+            module_path: "aoc::days",
+            file: "src/days.rs",
+            line: 1,
+        }
+    }
+
+    fn entries() -> Vec<&'static AocEntry> {
+        ENTRIES.iter().collect()
+    }
+
+    #[test]
+    fn prefers_year_specific_solutions() {
+        assert_eq!(
+            find_in(&entries(), 1, 1, 2024, None).unwrap().name,
+            "specific"
+        );
+        assert_eq!(
+            find_in(&entries(), 1, 1, 2023, None).unwrap().name,
+            "agnostic"
+        );
+        assert!(matches!(
+            find_in(&entries(), 5, 1, 2024, None),
+            Err(Error::NoSolution { day: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn reports_conflicting_solutions() {
+        let error = find_in(&entries(), 2, 1, 2024, None).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Conflicting solutions registered for Day 2 Part 1 of Year 2024: \
+             `aoc::days::first` (src/days.rs:30), `aoc::days::second` (src/days.rs:40)"
+        );
+        assert_eq!(
+            find_in(&entries(), 2, 1, 2023, None).unwrap().name,
+            "other_year"
+        );
+    }
+
+    #[test]
+    fn finds_every_conflict_in_the_registry() {
+        let conflicts = conflicts_in(&entries());
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!((conflicts[0].day, conflicts[0].variant), (4, Some("fast")));
+        assert_eq!((conflicts[1].day, conflicts[1].year), (2, Some(2024)));
+        let names: Vec<&str> = conflicts[1].entries.iter().map(|e| e.name).collect();
+        assert_eq!(names, ["first", "second"]);
+    }
+
+    #[test]
+    fn selects_variants() {
+        let find = |year, variant| find_in(&entries(), 3, 1, year, variant).map(|e| e.name);
+
+        assert_eq!(find(2024, None).unwrap(), "naive");
+        assert_eq!(find(2024, Some("fast")).unwrap(), "faster");
+        assert_eq!(find(2023, Some("fast")).unwrap(), "fast");
+
+        let error = find(2024, Some("slow")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No variant `slow` of Day 3 Part 1 for Year 2024 (available: default, fast)"
+        );
+        assert!(matches!(
+            find_in(&entries(), 4, 1, 2024, None),
+            Err(Error::UnknownVariant { variant: None, .. })
+        ));
+        assert!(matches!(
+            find_in(&entries(), 4, 1, 2024, Some("fast")),
+            Err(Error::ConflictingSolutions(_))
+        ));
+    }
+
+    #[test]
+    fn lists_variants_applying_to_a_year() {
+        let names = |year| -> Vec<&str> {
+            variants_in(&entries(), 3, 1, year)
+                .iter()
+                .map(|e| e.name)
+                .collect()
+        };
+
+        assert_eq!(names(2024), ["naive", "faster"]);
+        assert_eq!(names(2023), ["naive", "fast"]);
+    }
+
+    #[test]
+    fn lists_the_stars_of_a_year_with_a_default_solution() {
+        assert_eq!(stars_in(&entries(), 2024), [(1, 1), (2, 1), (3, 1)]);
+        assert_eq!(stars_in(&entries(), 2023), [(1, 1), (2, 1), (3, 1)]);
+        assert_eq!(stars_in(&entries()[..5], 2022), [(1, 1)]);
+    }
+
+    #[test]
+    fn finds_parsers() {
+        let parsers: Vec<&AocParser> = PARSERS.iter().collect();
+        let find = |day, year| find_parser_in(&parsers, day, year);
+
+        assert_eq!(find(7, Some(2024)).unwrap().name, "parse_2024");
+        assert_eq!(find(7, Some(2023)).unwrap().name, "parse");
+        assert_eq!(find(7, None).unwrap().name, "parse");
+        assert!(matches!(
+            find(9, Some(2024)),
+            Err(Error::NoParser {
+                day: 9,
+                year: Some(2024)
+            })
+        ));
+
+        let error = find(8, Some(2024)).unwrap_err();
+        assert_eq!(error.exit_code(), 8);
+        assert_eq!(
+            error.to_string(),
+            "Conflicting parsers registered for Day 8 of Year 2024: \
+             `aoc::days::first` (src/days.rs:1), `aoc::days::second` (src/days.rs:1)"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Human readable summaries of batch runs and comparisons of variants.
+
+use std::time::Duration;
+
+use crate::bench::BenchStats;
+use crate::examples::ExampleRun;
+use crate::runner::StarRun;
+
+/// Prints a table with the answer and timing of every run, followed by the
+/// number of stars that were computed successfully. When some days were
+/// parsed by a `#[star_parser]`, parsing times get their own column.
+pub(crate) fn print_summary(runs: &[StarRun]) {
+    let Some(year) = runs.first().map(|run| run.year) else {
+        println!("No solutions registered.");
+        return;
+    };
+
+    if runs.iter().any(|run| run.bench.is_some()) {
+        print_bench_summary(year, runs);
+        return;
+    }
+
+    let parsed = runs.iter().any(|run| run.parse.is_some());
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let (answer, time) = match &run.result {
+                Ok(answer) => (answer.clone(), format_duration(run.duration)),
+                Err(e) => (format!("error: {e}"), "-".to_string()),
+            };
+            let mut row = vec![run.day.to_string(), run.part.to_string(), answer];
+            if parsed {
+                row.push(run.parse.map_or_else(|| "-".to_string(), format_duration));
+            }
+            row.push(time);
+            row
+        })
+        .collect();
+
+    println!("Year {year}");
+    if parsed {
+        print_table(&["Day", "Part", "Answer", "Parse", "Time"], &rows, 2);
+    } else {
+        print_table(&["Day", "Part", "Answer", "Time"], &rows, 2);
+    }
+
+    let solved = runs.iter().filter(|run| run.result.is_ok()).count();
+    let total: Duration = runs
+        .iter()
+        .map(|run| run.duration + run.parse.unwrap_or_default())
+        .sum();
+    println!(
+        "{solved}/{} stars computed in {}",
+        runs.len(),
+        format_duration(total)
+    );
+}
+
+/// Prints the answer of each part of a day run with `--part both`, one line
+/// per part.
+pub(crate) fn print_parts(runs: &[StarRun]) {
+    for run in runs {
+        match &run.result {
+            Ok(answer) => println!("Part {}: {answer}", run.part),
+            Err(e) => println!("Part {}: error: {e}", run.part),
+        }
+    }
+}
+
+/// Prints the answers of a benchmarked batch, with the timing statistics of
+/// every day and part, and a total for the year.
+fn print_bench_summary(year: i32, runs: &[StarRun]) {
+    let durations =
+        |stats: &BenchStats| [stats.min, stats.median, stats.mean, stats.max].map(format_duration);
+
+    let mut rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let (answer, [min, median, mean, max]) = match (&run.result, &run.bench) {
+                (Ok(answer), Some(stats)) => (answer.clone(), durations(stats)),
+                (Ok(answer), None) => (answer.clone(), ["-"; 4].map(String::from)),
+                (Err(e), _) => (format!("error: {e}"), ["-"; 4].map(String::from)),
+            };
+            vec![
+                run.day.to_string(),
+                run.part.to_string(),
+                answer,
+                min,
+                median,
+                mean,
+                max,
+            ]
+        })
+        .collect();
+
+    let total = |field: fn(&BenchStats) -> Duration| {
+        format_duration(
+            runs.iter()
+                .filter_map(|run| run.bench.as_ref())
+                .map(field)
+                .sum(),
+        )
+    };
+    rows.push(vec![
+        String::new(),
+        String::new(),
+        "Total".to_string(),
+        total(|stats| stats.min),
+        total(|stats| stats.median),
+        total(|stats| stats.mean),
+        total(|stats| stats.max),
+    ]);
+
+    println!("Year {year}");
+    print_table(
+        &["Day", "Part", "Answer", "Min", "Median", "Mean", "Max"],
+        &rows,
+        2,
+    );
+
+    let solved = runs.iter().filter(|run| run.result.is_ok()).count();
+    let n = runs
+        .iter()
+        .find_map(|run| run.bench.as_ref())
+        .map_or(0, |stats| stats.runs);
+    println!("{solved}/{} stars computed, {n} runs each", runs.len());
+}
+
+/// Prints the answer and timing of every variant of a comparison, along with
+/// its time relative to the fastest variant. With `--bench`, the mean time is
+/// compared.
+pub(crate) fn print_comparison(runs: &[StarRun]) {
+    let fastest = runs
+        .iter()
+        .filter(|run| run.result.is_ok())
+        .map(|run| run.duration)
+        .min();
+
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let variant = run.variant.unwrap_or("default").to_string();
+            let (answer, time, relative) = match (&run.result, fastest) {
+                (Ok(answer), Some(fastest)) => (
+                    answer.clone(),
+                    format_duration(run.duration),
+                    relative_duration(run.duration, fastest),
+                ),
+                (Ok(answer), None) => (answer.clone(), "-".to_string(), "-".to_string()),
+                (Err(e), _) => (format!("error: {e}"), "-".to_string(), "-".to_string()),
+            };
+            vec![variant, answer, time, relative]
+        })
+        .collect();
+
+    let time = if runs.iter().any(|run| run.bench.is_some()) {
+        "Mean"
+    } else {
+        "Time"
+    };
+    print_table(&["Variant", "Answer", time, "Relative"], &rows, 0);
+}
+
+/// Prints whether each solution returned the expected answer on its example,
+/// followed by the number of examples that passed.
+pub(crate) fn print_examples(runs: &[ExampleRun]) {
+    if runs.is_empty() {
+        println!("No solutions with an example registered.");
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let entry = run.entry;
+            let year = entry
+                .year
+                .map_or_else(|| "any".to_string(), |y| y.to_string());
+            let mut name = entry.qualified_name();
+            if let Some(variant) = entry.variant {
+                name.push_str(&format!(" [{variant}]"));
+            }
+            let result = match &run.result {
+                _ if run.passed() => "ok".to_string(),
+                Ok(answer) => format!("expected `{}`, got `{answer}`", run.expected()),
+                Err(e) => format!("error: {e}"),
+            };
+            vec![
+                year,
+                entry.day.to_string(),
+                entry.part.to_string(),
+                name,
+                result,
+            ]
+        })
+        .collect();
+
+    print_table(&["Year", "Day", "Part", "Solution", "Result"], &rows, 3);
+    let passed = runs.iter().filter(|run| run.passed()).count();
+    println!("{passed}/{} examples passed", runs.len());
+}
+
+/// Formats how many times slower than `fastest` a duration is, e.g. `2.50x`.
+fn relative_duration(duration: Duration, fastest: Duration) -> String {
+    if fastest.is_zero() {
+        return "-".to_string();
+    }
+    format!("{:.2}x", duration.as_secs_f64() / fastest.as_secs_f64())
+}
+
+/// Prints the timing statistics of a single benchmarked solution.
+pub(crate) fn print_bench(stats: &BenchStats) {
+    println!(
+        "{} runs: min {} | median {} | mean {} | max {}",
+        stats.runs,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.mean),
+        format_duration(stats.max)
+    );
+}
+
+/// Prints an aligned table. The first `right_aligned` columns (such as day and
+/// part) are right aligned, the others left aligned.
+fn print_table(header: &[&str], rows: &[Vec<String>], right_aligned: usize) {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                if column < right_aligned {
+                    format!("{cell:>width$}")
+                } else {
+                    format!("{cell:<width$}")
+                }
+            })
+            .collect();
+        cells.join(" | ").trim_end().to_string()
+    };
+
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    println!("{}", format_row(&header));
+    let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", rule.join("-+-"));
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
+
+/// Formats a duration with a unit suited to its magnitude.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{secs:.2}s")
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.2}µs", secs * 1e6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_duration, relative_duration};
+
+    #[test]
+    fn formats_durations_with_suitable_units() {
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_nanos(1500)), "1.50µs");
+        assert_eq!(format_duration(Duration::ZERO), "0.00µs");
+    }
+
+    #[test]
+    fn formats_durations_relative_to_the_fastest() {
+        let fastest = Duration::from_millis(2);
+
+        assert_eq!(relative_duration(fastest, fastest), "1.00x");
+        assert_eq!(
+            relative_duration(Duration::from_millis(5), fastest),
+            "2.50x"
+        );
+        assert_eq!(relative_duration(fastest, Duration::ZERO), "-");
+    }
+}
@@ -1,130 +1,883 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
 use chrono::Datelike;
+use log::{debug, info, trace, warn};
 
+use crate::answers;
+use crate::bench::{BenchStats, bench};
 use crate::cli::CommandArgument;
+use crate::config::input_cache_paths;
+use crate::registry::{find_entry, find_parser, find_variants, stars_for_year};
+use crate::submissions::{Attempt, Bounds, History, Submission, format_wait, unlock_after};
+use crate::worker::{WorkerInput, map_concurrently};
 
-use crate::{AocEntry, config::get_config};
+use crate::{AocEntry, Error, ParsedInput, Rejection, SolutionResult, Solver, config::read_config};
 
 #[cfg(feature = "aoc-client")]
 use aoc_client::AocClient;
 
-pub fn run_with_result(
-    command_argument: CommandArgument,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let year = command_argument
-        .year
-        .unwrap_or_else(|| config_year().unwrap());
-
-    let day = command_argument.day.expect("Day is required");
-
-    let part = command_argument.part;
-
-    // We look for the corresponding entry in the inventory
-    // If no specific year match is found, we fall back to year-agnostic solutions
-    // If no solution is found, we panic
-    let mut entry: Option<&AocEntry> = None;
-    for e in crate::inventory::iter::<AocEntry> {
-        if e.day == day && e.part == part {
-            if let Some(e_year) = e.year {
-                if e_year == year {
-                    entry = Some(e);
-                    break;
+/// Outcome of running a single solution as part of a batch (`--all`) or of a
+/// comparison of variants (`--compare`).
+#[derive(Debug)]
+pub struct StarRun {
+    pub day: u32,
+    pub part: u32,
+    pub year: i32,
+    /// The variant that was run, `None` for the default one.
+    pub variant: Option<&'static str>,
+    /// The answer, or the reason why it could not be computed.
+    pub result: Result<String, Error>,
+    /// Time spent in the solution itself, excluding input loading and
+    /// parsing. When benchmarking, this is the mean over all runs.
+    pub duration: Duration,
+    /// Time spent parsing the input with the `#[star_parser]` of the day, on
+    /// the run that parsed it. The other parts of the day reuse the parsed
+    /// input.
+    pub parse: Option<Duration>,
+    /// Timing statistics, when the batch was run with `--bench`.
+    pub bench: Option<BenchStats>,
+}
+
+/// Where a [`Runner`] gets the puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Input {
+    /// The cached input of the day (`input/{day}_{year}.txt` by default, see
+    /// `inputs_dir` and `input_pattern` in the config), downloaded from
+    /// Advent of Code first if it is not cached yet. Downloading requires
+    /// the `aoc-client` feature.
+    #[default]
+    Remote,
+    /// The contents of the given file.
+    File(PathBuf),
+    /// The standard input, read until its end.
+    Stdin,
+    /// The given text.
+    Text(String),
+}
+
+/// Outcome of a solution run by a [`Runner`].
+#[derive(Debug)]
+pub struct RunOutcome {
+    /// The answer returned by the solution.
+    pub answer: String,
+    /// Time spent in the solution itself, excluding input loading and
+    /// parsing.
+    pub duration: Duration,
+    /// Time spent parsing the input with the `#[star_parser]` of the day, if
+    /// the solution borrows the parsed input.
+    pub parse: Option<Duration>,
+    /// The solution that was run.
+    pub entry: &'static AocEntry,
+    /// The year the solution was run for: the one given to the runner, or
+    /// the configured one (the current year by default).
+    pub year_resolved: i32,
+}
+
+/// Runs a registered solution from code, without parsing the command line or
+/// writing to stdout.
+///
+/// The part defaults to 1, the year to the configured one and the input to
+/// [`Input::Remote`]; only the day is required.
+///
+/// ```no_run
+/// use aoc_star::{Input, Runner};
+///
+/// let outcome = Runner::new()
+///     .year(2024)
+///     .day(3)
+///     .part(2)
+///     .input(Input::File("input/3_2024.txt".into()))
+///     .run()?;
+/// println!("{} ({:?})", outcome.answer, outcome.duration);
+/// # Ok::<(), aoc_star::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Runner {
+    day: Option<u32>,
+    part: u32,
+    year: Option<i32>,
+    variant: Option<String>,
+    input: Input,
+    timeout: Option<Duration>,
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Runner::new()
+    }
+}
+
+impl Runner {
+    /// A runner for part 1 of the configured year, on the remote input. The
+    /// day must be set with [`day`](Runner::day).
+    pub fn new() -> Self {
+        Runner {
+            day: None,
+            part: 1,
+            year: None,
+            variant: None,
+            input: Input::Remote,
+            timeout: None,
+        }
+    }
+
+    /// Sets the day to run.
+    pub fn day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Sets the part to run.
+    pub fn part(mut self, part: u32) -> Self {
+        self.part = part;
+        self
+    }
+
+    /// Sets the year to run the solution for, instead of the configured one.
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Runs the named variant of the solution instead of the default one.
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Sets where the puzzle input comes from.
+    pub fn input(mut self, input: Input) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Gives up on the solution if it does not finish within `timeout`,
+    /// unless it has a budget of its own (`timeout` in its `#[star]`
+    /// attribute).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Finds the solution to run and the year it is run for, without running
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DayMissing`] if no day was set, and the errors of the
+    /// registry lookup otherwise (no solution, unknown variant, conflicting
+    /// registrations), or of reading the config for the default year.
+    pub fn resolve(&self) -> Result<(&'static AocEntry, i32), Error> {
+        let day = self.day.ok_or(Error::DayMissing)?;
+        let year = resolve_year(self.year)?;
+        let entry = find_entry(day, self.part, year, self.variant.as_deref())?;
+        Ok((entry, year))
+    }
+
+    /// Loads the input and runs the solution on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`resolve`](Runner::resolve), of loading the
+    /// input, and of the solution or its parser, and [`Error::Timeout`] if the
+    /// solution exceeds its budget.
+    pub fn run(&self) -> Result<RunOutcome, Error> {
+        let (entry, year) = self.resolve()?;
+        self.run_entry(entry, year)
+    }
+
+    /// Runs the given solution, as resolved by [`resolve`](Runner::resolve).
+    fn run_entry(&self, entry: &'static AocEntry, year: i32) -> Result<RunOutcome, Error> {
+        let mut input = WorkerInput::new(get_input(entry.day, year, &self.input)?, self.timeout);
+        let run = input.run(entry, year, None);
+        Ok(RunOutcome {
+            answer: run.result?,
+            duration: run.duration,
+            parse: run.parse,
+            entry,
+            year_resolved: year,
+        })
+    }
+}
+
+/// The runner for the solution requested on the command line.
+fn runner_for(command_argument: &CommandArgument) -> Result<Runner, Error> {
+    let part = single_part(command_argument)?;
+    let mut runner = Runner::new()
+        .day(command_argument.day.ok_or(Error::DayMissing)?)
+        .part(part)
+        .input(input_for(command_argument));
+    runner.year = command_argument.year;
+    runner.variant = command_argument.variant.clone();
+    runner.timeout = command_argument.timeout;
+    Ok(runner)
+}
+
+/// The part requested on the command line, which must not be `both`.
+fn single_part(command_argument: &CommandArgument) -> Result<u32, Error> {
+    command_argument
+        .part
+        .single()
+        .ok_or(Error::SinglePartRequired)
+}
+
+/// The input requested on the command line: `--input-file`, the standard
+/// input with `--input-file -`, or the cached (or remote) input.
+fn input_for(command_argument: &CommandArgument) -> Input {
+    match command_argument.input_file.as_deref() {
+        Some("-") => Input::Stdin,
+        Some(filename) => Input::File(filename.into()),
+        None => Input::Remote,
+    }
+}
+
+/// Runs (or benchmarks, with `--bench`) the solution requested on the
+/// command line, logging it first. The error of the solution itself is
+/// recorded in the returned [`StarRun`].
+pub(crate) fn run_requested(
+    command_argument: &CommandArgument,
+) -> Result<(&'static AocEntry, StarRun), Error> {
+    let runner = runner_for(command_argument)?;
+    let (entry, year) = runner.resolve()?;
+    info!(
+        "Executing Day {} Part {} of Year {year} (`{}`)",
+        entry.day,
+        entry.part,
+        entry.qualified_name()
+    );
+
+    let mut input = WorkerInput::new(get_input(entry.day, year, &runner.input)?, runner.timeout);
+    Ok((entry, input.run(entry, year, command_argument.bench)))
+}
+
+/// Runs the solution requested on the command line, logging it first, and
+/// publishes the answer with `--publish`.
+#[cfg(any(test, feature = "test-helpers"))]
+pub fn run_with_result(command_argument: CommandArgument) -> Result<String, Error> {
+    let (entry, run) = run_requested(&command_argument)?;
+    let answer = run.result?;
+    // If `publish` is true, the result is submitted to Advent of Code, but
+    // only if the `aoc-client` feature is enabled
+    if command_argument.publish {
+        publish(entry, run.year, &answer, command_argument.wait)?;
+    }
+
+    Ok(answer)
+}
+
+/// Benchmarks the requested solution (`--bench`), running it as many times
+/// as requested on the same input. Input loading is not part of the timings.
+#[cfg(any(test, feature = "test-helpers"))]
+pub fn bench_with_result(
+    mut command_argument: CommandArgument,
+) -> Result<(String, BenchStats), Error> {
+    command_argument.bench = Some(command_argument.bench.unwrap_or(1));
+    let (_, run) = run_requested(&command_argument)?;
+    Ok((
+        run.result?,
+        run.bench.expect("the solution was benchmarked"),
+    ))
+}
+
+/// Runs every solution registered for the requested year (or the configured
+/// one), in day and part order.
+///
+/// A failing day (including one with conflicting solutions) does not stop
+/// the batch: its error is recorded in the corresponding [`StarRun`]. Only
+/// failing to resolve the year is an error.
+/// The input of each day is the cached (or remote) one, unless `input_file`
+/// is given (only from code: the command line does not accept it with
+/// `--all`), in which case every day reads that file.
+/// With `--bench`, each solution is benchmarked instead of run once.
+/// With `--jobs N`, up to `N` days run at once; the runs are still returned
+/// in day and part order. With `--serial-timing`, only one solution runs at a
+/// time, so that only loading the inputs is concurrent.
+pub fn run_all(command_argument: CommandArgument) -> Result<Vec<StarRun>, Error> {
+    let year = resolve_year(command_argument.year)?;
+
+    let mut days: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for (day, part) in stars_for_year(year) {
+        days.entry(day).or_default().push(part);
+    }
+    let jobs = command_argument.jobs.unwrap_or(1);
+    if jobs > 1 {
+        info!("Running {} days on {jobs} threads", days.len());
+    }
+
+    let serial = command_argument.serial_timing.then(|| Mutex::new(()));
+    let runs = map_concurrently(days.into_iter().collect(), jobs as usize, |(day, parts)| {
+        run_day(day, &parts, year, &command_argument, serial.as_ref())
+    });
+    Ok(runs.into_iter().flatten().collect())
+}
+
+/// Runs the given parts of a day for [`run_all`]. Both parts share the same
+/// input, so it is only loaded (and parsed) once. With `serial`, the
+/// solutions only run while holding it.
+fn run_day(
+    day: u32,
+    parts: &[u32],
+    year: i32,
+    command_argument: &CommandArgument,
+    serial: Option<&Mutex<()>>,
+) -> Vec<StarRun> {
+    let mut input: Option<WorkerInput> = None;
+    let mut runs = Vec::new();
+    for &part in parts {
+        let entry = match find_entry(day, part, year, None) {
+            Ok(entry) => entry,
+            Err(e) => {
+                runs.push(StarRun {
+                    day,
+                    part,
+                    year,
+                    variant: None,
+                    result: Err(e),
+                    duration: Duration::ZERO,
+                    parse: None,
+                    bench: None,
+                });
+                continue;
+            }
+        };
+        if input.is_none() {
+            match get_input(day, year, &input_for(command_argument)) {
+                Ok(contents) => {
+                    input = Some(WorkerInput::new(contents, command_argument.timeout));
+                }
+                Err(e) => {
+                    runs.push(StarRun {
+                        day,
+                        part,
+                        year,
+                        variant: entry.variant,
+                        result: Err(e),
+                        duration: Duration::ZERO,
+                        parse: None,
+                        bench: None,
+                    });
+                    continue;
                 }
-            } else if entry.is_none() {
-                entry = Some(e);
             }
         }
+        let input = input.as_mut().expect("the input of the day is loaded");
+
+        let _turn = serial.map(|serial| serial.lock().unwrap_or_else(PoisonError::into_inner));
+        runs.push(input.run(entry, year, command_argument.bench));
+    }
+    runs
+}
+
+/// Runs both parts of the requested day on the same input (`--part both`),
+/// parsing it at most once.
+///
+/// A failing part does not stop the other one: its error is recorded in the
+/// corresponding [`StarRun`]. Day 25 only has a first part, so a missing
+/// second part is not reported for it. Only a missing day, failing to resolve
+/// the year, finding no solution at all or loading the input is an error.
+pub fn run_both_parts(command_argument: CommandArgument) -> Result<Vec<StarRun>, Error> {
+    let day = command_argument.day.ok_or(Error::DayMissing)?;
+    let year = resolve_year(command_argument.year)?;
+    let variant = command_argument.variant.as_deref();
+
+    let mut entries: Vec<(u32, Result<&AocEntry, Error>)> = [1, 2]
+        .into_iter()
+        .map(|part| (part, find_entry(day, part, year, variant)))
+        .collect();
+    if day == 25 {
+        entries
+            .retain(|(part, entry)| *part == 1 || !matches!(entry, Err(Error::NoSolution { .. })));
+    }
+
+    let Some(first) = entries.iter().find_map(|(_, entry)| entry.as_ref().ok()) else {
+        let (_, entry) = entries.remove(0);
+        return Err(entry.expect_err("no part was found"));
+    };
+    info!("Executing Day {day} of Year {year}");
+
+    let mut input = WorkerInput::new(
+        get_input(first.day, year, &input_for(&command_argument))?,
+        command_argument.timeout,
+    );
+    Ok(entries
+        .into_iter()
+        .map(|(part, entry)| match entry {
+            Ok(entry) => input.run(entry, year, None),
+            Err(e) => StarRun {
+                day,
+                part,
+                year,
+                variant: None,
+                result: Err(e),
+                duration: Duration::ZERO,
+                parse: None,
+                bench: None,
+            },
+        })
+        .collect())
+}
+
+/// Submits the answer of the first part of the day that Advent of Code does
+/// not consider solved yet, among the given runs of both parts.
+///
+/// The history of submissions tells which parts are solved; the puzzle page
+/// is only fetched when it does not know of an accepted answer for part 1.
+/// Nothing is submitted if both parts are already solved, or if the first
+/// unsolved part did not produce an answer (its error is reported with the
+/// runs). Returns the part that was submitted, if any.
+pub(crate) fn publish_first_unsolved(runs: &[StarRun], wait: bool) -> Result<Option<u32>, Error> {
+    let Some(&StarRun { day, year, .. }) = runs.first() else {
+        return Ok(None);
+    };
+    let unsolved = match History::load()?.unsolved_part(year, day) {
+        Some(unsolved) => unsolved,
+        None => first_unsolved_part(day, year)?,
+    };
+    let Some(part) = unsolved else {
+        warn!("Both parts of Day {day} of Year {year} are already solved, nothing to submit");
+        return Ok(None);
+    };
+
+    match runs.iter().find(|run| run.part == part) {
+        Some(
+            run @ StarRun {
+                result: Ok(answer), ..
+            },
+        ) => {
+            let entry = find_entry(day, part, year, run.variant)?;
+            publish(entry, year, answer, wait)
+        }
+        _ => {
+            warn!("Part {part} is the first unsolved part, but has no answer to submit");
+            Ok(None)
+        }
+    }
+}
+
+/// Submits the answer of a solution to Advent of Code, returning the part
+/// that was submitted, and appends the outcome to the history of
+/// submissions.
+///
+/// The history is checked first: the answer is not submitted if the part is
+/// already solved, or if a previous submission shows that it is wrong. Nor
+/// is it submitted while Advent of Code asks to wait after a previous
+/// submission, unless `wait` is set, in which case it is submitted once the
+/// puzzle unlocks.
+pub(crate) fn publish(
+    entry: &AocEntry,
+    year: i32,
+    answer: &str,
+    wait: bool,
+) -> Result<Option<u32>, Error> {
+    let (day, part) = (entry.day, entry.part);
+    let mut history = History::load()?;
+    if let Some(accepted) = history.accepted(year, day, part) {
+        if accepted != answer {
+            return Err(Error::AnswerMismatch {
+                day,
+                part,
+                year,
+                expected: accepted.to_string(),
+                found: answer.to_string(),
+            });
+        }
+        info!("`{answer}` was already accepted for Day {day} Part {part} of Year {year}");
+        return Ok(Some(part));
+    }
+    history.check(year, day, part, answer)?;
+    let bounds = history.bounds(year, day, part);
+    if bounds != Bounds::default() {
+        info!("Previous attempts show that the answer is {bounds}");
+    }
+
+    loop {
+        let now = chrono::Utc::now();
+        if let Some(unlock) = history.unlock(year, day, now) {
+            let remaining = (unlock - now).to_std().unwrap_or_default();
+            if !wait {
+                return Err(Error::SubmissionLocked {
+                    day,
+                    year,
+                    remaining,
+                });
+            }
+            info!("Waiting {} before submitting", format_wait(remaining));
+            std::thread::sleep(remaining);
+        }
+
+        info!("Submitting `{answer}` for Day {day} Part {part} of Year {year}");
+        let at = chrono::Utc::now();
+        let (outcome, cooldown) = publish_result(entry, year, answer)?;
+        history.append(Attempt {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            at,
+            unlock: cooldown.map(|cooldown| unlock_after(at, cooldown)),
+        })?;
+        match outcome {
+            Submission::Correct => break,
+            // Submitted too early: the answer was not checked, try again
+            // once the puzzle unlocks
+            Submission::Rejected(Rejection::TooRecent) if wait && cooldown.is_some() => {}
+            Submission::Rejected(reason) => {
+                return Err(Error::SubmissionRejected {
+                    day,
+                    part,
+                    year,
+                    reason,
+                });
+            }
+        }
+    }
+    info!("That's the right answer!");
+    // The answer is accepted either way: failing to record it only costs
+    // `--verify` a known answer
+    if let Err(e) = answers::record(year, day, part, answer) {
+        warn!("Could not record the answer of Day {day} Part {part} of Year {year}: {e}");
     }
+    Ok(Some(part))
+}
+
+/// Runs every variant of the requested solution on the same input
+/// (`--compare`), benchmarking each of them with `--bench`.
+///
+/// A failing variant does not stop the comparison: its error is recorded in
+/// the corresponding [`StarRun`]. The runs are sorted by variant name, the
+/// default variant first. A missing day or `--part both` is an error
+/// ([`Error::DayMissing`], [`Error::SinglePartRequired`]).
+pub fn compare_variants(command_argument: CommandArgument) -> Result<Vec<StarRun>, Error> {
+    let day = command_argument.day.ok_or(Error::DayMissing)?;
+    let part = single_part(&command_argument)?;
+    let year = resolve_year(command_argument.year)?;
+
+    let variants = find_variants(day, part, year);
+    let Some(first) = variants.first() else {
+        return Err(Error::NoSolution { day, part, year });
+    };
+    info!(
+        "Comparing {} variants of Day {day} Part {part} of Year {year}",
+        variants.len()
+    );
 
-    let entry = entry
-        .unwrap_or_else(|| panic!("No solution found for Day {day} Part {part} of Year {year}"));
+    let mut input = WorkerInput::new(
+        get_input(first.day, year, &input_for(&command_argument))?,
+        command_argument.timeout,
+    );
+    Ok(variants
+        .into_iter()
+        .map(|entry| input.run(entry, year, command_argument.bench))
+        .collect())
+}
+
+/// Checks that every variant of a comparison succeeded with the same answer,
+/// which is returned. The first failing variant is returned as the error.
+///
+/// # Panics
+///
+/// Panics if `runs` is empty.
+pub(crate) fn check_agreement(runs: Vec<StarRun>) -> Result<String, Error> {
+    let &StarRun {
+        day, part, year, ..
+    } = runs.first().expect("no variant was run");
 
-    println!("Executing Day {day} Part {part} of Year {year}");
+    let answers = runs
+        .into_iter()
+        .map(|run| Ok((run.variant.unwrap_or("default").to_string(), run.result?)))
+        .collect::<Result<Vec<(String, String)>, Error>>()?;
 
-    // We run the day with the provided arguments
-    run_day(
-        entry,
-        command_argument.publish,
-        &command_argument.input_file,
-    )
+    let answer = &answers[0].1;
+    if answers.iter().all(|(_, other)| other == answer) {
+        Ok(answer.clone())
+    } else {
+        Err(Error::VariantsDisagree {
+            day,
+            part,
+            year,
+            answers,
+        })
+    }
 }
-/// Retrieves the configured year or defaults to the current year.
-pub(crate) fn config_year() -> Result<i32, Box<dyn std::error::Error>> {
-    Ok(get_config()?
+
+/// Solves (or benchmarks, if `bench_runs` is given) a single solution of a
+/// batch. When benchmarking, `ran` is called after each run.
+pub(crate) fn run_star(
+    entry: &AocEntry,
+    year: i32,
+    input: &mut DayInput,
+    bench_runs: Option<u32>,
+    ran: impl FnMut(),
+) -> StarRun {
+    let parsed_before = input.parse_duration.is_some();
+    let (result, duration, stats) = match bench_runs {
+        Some(n) => match bench(entry, year, input, n, ran) {
+            Ok((answer, stats)) => (Ok(answer), stats.mean, Some(stats)),
+            Err(e) => (Err(e), Duration::ZERO, None),
+        },
+        None => {
+            let (result, duration) = solve(entry, year, input);
+            (result, duration, None)
+        }
+    };
+    StarRun {
+        day: entry.day,
+        part: entry.part,
+        year,
+        variant: entry.variant,
+        result,
+        duration,
+        parse: input.parse_duration.filter(|_| !parsed_before),
+        bench: stats,
+    }
+}
+
+/// Uses the given year, or falls back to the configured one.
+pub(crate) fn resolve_year(year: Option<i32>) -> Result<i32, Error> {
+    match year {
+        Some(year) => Ok(year),
+        None => config_year(),
+    }
+}
+
+/// Retrieves the configured year or defaults to the current year. No config
+/// file is created if there is none.
+pub(crate) fn config_year() -> Result<i32, Error> {
+    Ok(read_config()?
         .year
         .unwrap_or_else(|| chrono::Utc::now().year()))
 }
 
-/// Executes the given AocEntry with the provided options.
-/// If `publish` is true, the result will be submitted to Advent of Code,
-/// but only if the `aoc-client` feature is enabled.
-pub(crate) fn run_day(
-    entry: &AocEntry,
-    publish: bool,
-    input_file: &Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let result = (entry.func)(get_input(entry, input_file)?);
+/// The input of a day, along with its parsed value once a solution borrowing
+/// it has run. The solutions of a day share it, so that the input is parsed
+/// at most once.
+pub(crate) struct DayInput {
+    raw: String,
+    parsed: Option<ParsedInput>,
+    /// Time spent parsing the input, once parsed.
+    pub(crate) parse_duration: Option<Duration>,
+}
 
-    if publish {
-        publish_result(entry, &result)?;
+impl DayInput {
+    pub(crate) fn new(raw: String) -> Self {
+        DayInput {
+            raw,
+            parsed: None,
+            parse_duration: None,
+        }
     }
 
-    Ok(result)
+    /// The input parsed by the parser of `day` for `year`, parsing it first if
+    /// no solution needed it yet.
+    fn parsed(&mut self, day: u32, year: i32) -> Result<&ParsedInput, Error> {
+        if self.parsed.is_none() {
+            let parser = find_parser(day, Some(year))?;
+            let raw = self.raw.clone();
+
+            let start = Instant::now();
+            let parsed = (parser.func)(raw);
+            let duration = start.elapsed();
+            debug!(
+                "Parsed the input with `{}` in {duration:?}",
+                parser.qualified_name()
+            );
+
+            self.parsed = Some(parsed.map_err(|source| Error::Parse {
+                day,
+                year,
+                parser: parser.qualified_name(),
+                source,
+            })?);
+            self.parse_duration = Some(duration);
+        }
+        Ok(self.parsed.as_ref().expect("the input was just parsed"))
+    }
 }
-/// Retrieves the input for the given AocEntry.
-/// If `input_file` is provided, reads the input from the file.
-/// Otherwise, fetches the input remotely using the aoc-client crate
-fn get_input(
+
+/// Runs the solution on the given input, measuring how long it takes.
+/// Solutions borrowing the parsed input get it from `input`, which is parsed
+/// beforehand if needed; parsing is not part of the measured duration.
+pub(crate) fn solve(
     entry: &AocEntry,
-    input_file: &Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    match input_file {
-        Some(filename) => Ok(std::fs::read_to_string(filename)?),
-        None => get_remote_input(entry),
+    year: i32,
+    input: &mut DayInput,
+) -> (Result<String, Error>, Duration) {
+    let (result, duration) = match entry.func {
+        Solver::Raw(func) => {
+            let raw = input.raw.clone();
+            let start = Instant::now();
+            let result = func(raw);
+            (result, start.elapsed())
+        }
+        Solver::Parsed { func, input_type } => {
+            let parsed = match input.parsed(entry.day, year) {
+                Ok(parsed) => parsed,
+                Err(e) => return (Err(e), Duration::ZERO),
+            };
+
+            let start = Instant::now();
+            let result = func(parsed.value());
+            let duration = start.elapsed();
+
+            match result {
+                Some(result) => (result, duration),
+                None => {
+                    let mismatch = Error::ParsedInputMismatch {
+                        day: entry.day,
+                        part: entry.part,
+                        solution: entry.qualified_name(),
+                        expected: input_type(),
+                        found: parsed.type_name(),
+                    };
+                    return (Err(mismatch), Duration::ZERO);
+                }
+            }
+        }
+    };
+
+    let result = result.map_err(|source| Error::Solution {
+        day: entry.day,
+        part: entry.part,
+        year,
+        solution: entry.qualified_name(),
+        source,
+    });
+    trace!("`{}` ran in {duration:?}", entry.qualified_name());
+    (result, duration)
+}
+
+/// Runs a solution borrowing the parsed input on its example, as generated by
+/// the `#[star]` macro for `cargo test`.
+pub fn solve_parsed_example(
+    day: u32,
+    year: Option<i32>,
+    input: &str,
+    func: fn(&dyn Any) -> Option<SolutionResult>,
+) -> SolutionResult {
+    let parser = find_parser(day, year)?;
+    let parsed = (parser.func)(input.to_string())?;
+    match func(parsed.value()) {
+        Some(answer) => answer,
+        None => Err(format!(
+            "the parser `{}` returns a `{}`, which the solution does not borrow",
+            parser.qualified_name(),
+            parsed.type_name()
+        )
+        .into()),
     }
 }
-#[cfg(not(feature = "aoc-client"))]
-fn get_remote_input(_: &AocEntry) -> Result<String, Box<dyn std::error::Error>> {
-    panic!(
-        "Remote input is disabled. \
-         Enable the `aoc-client` feature or provide an input file."
+
+/// Retrieves the input of the given day and year.
+/// Files are read, text is used as is, and the remote input is fetched using
+/// the aoc-client crate.
+fn get_input(day: u32, year: i32, input: &Input) -> Result<String, Error> {
+    match input {
+        Input::File(filename) => {
+            debug!("Reading the input from {}", filename.display());
+            read_input(filename)
+        }
+        Input::Stdin => {
+            debug!("Reading the input from stdin");
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Input::Text(text) => Ok(text.clone()),
+        Input::Remote => get_cached_input(day, year),
+    }
+}
+
+/// Downloads the input of a day into the input cache (`fetch`), unless it is
+/// already there.
+pub(crate) fn fetch_input(day: u32, year: Option<i32>) -> Result<(), Error> {
+    let year = resolve_year(year)?;
+    let input = get_cached_input(day, year)?;
+    info!(
+        "Fetched the input of Day {day} of Year {year} ({} lines)",
+        input.lines().count()
     );
+    Ok(())
 }
 
-#[cfg(feature = "aoc-client")]
-fn get_remote_input(entry: &AocEntry) -> Result<String, Box<dyn std::error::Error>> {
-    use std::path::PathBuf;
+/// Reads the input of a day from the input cache, downloading it into the
+/// cache first if it is not there and remote input is enabled.
+fn get_cached_input(day: u32, year: i32) -> Result<String, Error> {
+    read_or_download(day, year, input_cache_paths(day, year)?)
+}
 
-    let mut input_path = PathBuf::from("input");
-    std::fs::create_dir_all(&input_path)?;
-    input_path.push(format!("{}_{}.txt", entry.day, resolve_year(entry)?));
+/// Reads the first of the cached inputs at `paths` that exists, or downloads
+/// the input into the first one.
+fn read_or_download(day: u32, year: i32, paths: Vec<PathBuf>) -> Result<String, Error> {
+    if let Some(path) = paths.iter().find(|path| path.exists()) {
+        debug!("Reading the cached input from {}", path.display());
+        return read_input(path);
+    }
+    download_input(day, year, paths)
+}
 
-    if input_path.exists() {
-        let input = std::fs::read_to_string(&input_path)?;
+/// Reads an input file, reporting its path if that fails.
+fn read_input(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
-        return Ok(input);
-    }
+#[cfg(not(feature = "aoc-client"))]
+fn download_input(day: u32, year: i32, tried: Vec<PathBuf>) -> Result<String, Error> {
+    Err(Error::InputUnavailable {
+        day,
+        year,
+        tried,
+        download: None,
+    })
+}
 
-    let input = build_aoc_client(entry)?.get_input()?;
+#[cfg(feature = "aoc-client")]
+fn download_input(day: u32, year: i32, paths: Vec<PathBuf>) -> Result<String, Error> {
+    let input_path = &paths[0];
+    debug!("Downloading the input of Day {day} of Year {year}");
+    let input = build_aoc_client(day, year)
+        .and_then(|client| Ok(client.get_input()?))
+        .map_err(|cause| Error::InputUnavailable {
+            day,
+            year,
+            tried: paths.clone(),
+            download: Some(Box::new(cause)),
+        })?;
+    debug!("Caching the input in {}", input_path.display());
+    if let Some(dir) = input_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
     std::fs::write(input_path, &input)?;
 
     Ok(input)
 }
 
 #[cfg(feature = "aoc-client")]
-/// Builds an AocClient for the given AocEntry.
-fn build_aoc_client(entry: &AocEntry) -> Result<AocClient, Box<dyn std::error::Error>> {
-    use aoc_client::AocClient;
-    let cookie = get_cookie()?;
+/// Builds an AocClient for the given day and year.
+fn build_aoc_client(day: u32, year: i32) -> Result<AocClient, Error> {
+    aoc_client_with(&get_cookie()?, day, year)
+}
 
+#[cfg(feature = "aoc-client")]
+/// Builds an Advent of Code client for a day with the given session cookie.
+fn aoc_client_with(cookie: &str, day: u32, year: i32) -> Result<AocClient, Error> {
     Ok(AocClient::builder()
         .session_cookie(cookie)?
-        .year(resolve_year(entry)?)?
-        .day(entry.day)?
+        .year(year)?
+        .day(day)?
         .build()?)
 }
 #[cfg(feature = "aoc-client")]
 /// Retrieves the session cookie from the config file or environment variable.
-fn get_cookie() -> Result<String, Box<dyn std::error::Error>> {
+fn get_cookie() -> Result<String, Error> {
     use crate::config::get_config_token;
     let config_cookie = get_config_token()?;
     if !config_cookie.is_empty() {
@@ -132,87 +885,231 @@ fn get_cookie() -> Result<String, Box<dyn std::error::Error>> {
     } else if let Ok(env_cookie) = std::env::var("AOC_TOKEN").as_ref() {
         Ok(env_cookie.clone())
     } else {
-        Err(Box::new(std::io::Error::other(
-            "AOC session cookie is missing. Please set it in the config file or AOC_TOKEN environment variable.",
-        )))
+        Err(Error::ConfigMissing { key: "token" })
     }
 }
+
+#[cfg(not(feature = "aoc-client"))]
+/// Stub function for checking solved parts when the aoc-client feature is
+/// disabled.
+fn first_unsolved_part(_: u32, _: i32) -> Result<Option<u32>, Error> {
+    Err(Error::PublishUnavailable)
+}
+
 #[cfg(feature = "aoc-client")]
-/// Resolves the year for the given AocEntry.
-fn resolve_year(entry: &AocEntry) -> Result<i32, Box<dyn std::error::Error>> {
-    match entry.year {
-        Some(year) => Ok(year),
-        None => config_year(),
-    }
+/// Finds the first part of the day that is not solved yet, from the puzzle
+/// page of the logged-in user. `None` means both parts are solved.
+fn first_unsolved_part(day: u32, year: i32) -> Result<Option<u32>, Error> {
+    debug!("Fetching the puzzle page of Day {day} of Year {year}");
+    let html = build_aoc_client(day, year)?.get_puzzle_html()?;
+
+    let part = unsolved_part(&html);
+    debug!("First unsolved part: {part:?}");
+    Ok(part)
+}
+
+#[cfg(feature = "aoc-client")]
+/// The first unsolved part, given the HTML of a puzzle page: every solved part
+/// is followed by a "Your puzzle answer was" paragraph.
+fn unsolved_part(html: &str) -> Option<u32> {
+    let solved = html.matches("Your puzzle answer was").count() as u32;
+    (solved < 2).then_some(solved + 1)
 }
 
 #[cfg(not(feature = "aoc-client"))]
 /// Stub function for publishing results when the aoc-client feature is disabled.
-fn publish_result(_: &AocEntry, _: &str) -> Result<(), Box<dyn std::error::Error>> {
-    panic!("Publishing answers requires the `aoc-client` feature to be enabled.");
+fn publish_result(_: &AocEntry, _: i32, _: &str) -> Result<(Submission, Option<Duration>), Error> {
+    Err(Error::PublishUnavailable)
 }
 
 #[cfg(feature = "aoc-client")]
-/// Publishes the result to Advent of Code using the aoc-client crate.
-fn publish_result(entry: &AocEntry, result: &str) -> Result<(), Box<dyn std::error::Error>> {
-    build_aoc_client(entry)?.submit_answer_and_show_outcome(&entry.part.to_string(), result)?;
-    Ok(())
+/// Publishes the result to Advent of Code, returning how the submission went
+/// and how long Advent of Code asked to wait before submitting again, if it
+/// did.
+fn publish_result(
+    entry: &AocEntry,
+    year: i32,
+    result: &str,
+) -> Result<(Submission, Option<Duration>), Error> {
+    use crate::submissions::submission_outcome;
+
+    let cookie = get_cookie()?;
+    let client = aoc_client_with(&cookie, entry.day, year)?;
+    if !client.day_unlocked() {
+        return Err(aoc_client::AocError::LockedPuzzle(entry.day, year).into());
+    }
+    let html = post_answer(&cookie, entry.day, entry.part, year, result)?;
+    let outcome = submission_outcome(&html).ok_or(aoc_client::AocError::AocResponseError)?;
+    debug!("Submission outcome: {outcome:?}");
+    Ok(outcome)
+}
+
+#[cfg(feature = "aoc-client")]
+/// Posts an answer to Advent of Code and returns the page it answers with.
+///
+/// This is the one request not sent by aoc-client. Its `submit_answer` only
+/// returns a coarse outcome, without whether a wrong answer is too high or
+/// too low nor how long to wait before the next submission, which the
+/// history of submissions needs (the method returning the page is private in
+/// aoc-client 0.2). The request is built as aoc-client builds its own (same
+/// cookie, no redirects), with the reqwest it already depends on, so no
+/// other HTTP stack is pulled in. Switch to aoc-client once it exposes the
+/// page.
+fn post_answer(
+    cookie: &str,
+    day: u32,
+    part: u32,
+    year: i32,
+    answer: &str,
+) -> Result<String, Error> {
+    use aoc_client::AocError;
+    use reqwest::header::{COOKIE, USER_AGENT};
+
+    let client = reqwest::blocking::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(AocError::from)?;
+    client
+        .post(format!("https://adventofcode.com/{year}/day/{day}/answer"))
+        .header(COOKIE, format!("session={}", cookie.trim()))
+        .header(
+            USER_AGENT,
+            concat!(env!("CARGO_PKG_REPOSITORY"), " ", env!("CARGO_PKG_VERSION")),
+        )
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| AocError::from(e).into())
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::Write;
 
-    use crate::AocEntry;
+    use crate::runner::Input;
     use tempfile::NamedTempFile;
 
     #[test]
     fn get_input_reads_from_file_when_provided() {
         let mut tmp = NamedTempFile::new().unwrap();
         tmp.write_fmt(format_args!("hello world")).unwrap();
-        let path = tmp.path().to_str().unwrap().to_string();
-
-        let entry = AocEntry {
-            day: 1,
-            part: 1,
-            year: None,
-            func: |s| s,
-        };
+        let path = tmp.path().to_path_buf();
 
-        let input = super::get_input(&entry, &Some(path)).unwrap();
+        let input = super::get_input(1, 2024, &Input::File(path)).unwrap();
         assert!(input.contains("hello world"));
     }
 
     #[test]
-    #[should_panic(expected = "Remote input is disabled")]
+    fn get_input_uses_text_as_is() {
+        let input = super::get_input(1, 2024, &Input::Text("1\n2\n".to_string())).unwrap();
+        assert_eq!(input, "1\n2\n");
+    }
+
+    #[test]
+    fn reads_the_cached_input() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"cached").unwrap();
+
+        let missing = tmp.path().with_extension("missing");
+        let paths = vec![missing, tmp.path().to_path_buf()];
+        let input = super::read_or_download(1, 2024, paths).unwrap();
+        assert_eq!(input, "cached");
+    }
+
+    #[test]
     #[cfg(not(feature = "aoc-client"))]
-    fn get_input_panics_without_aoc_client_and_no_file() {
-        use crate::runner::get_input;
+    fn get_input_fails_without_aoc_client_and_no_cached_input() {
+        use crate::Error;
 
-        let entry = AocEntry {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = vec![dir.path().join("1_2024.txt"), dir.path().join("day01.txt")];
+
+        let error = super::read_or_download(1, 2024, paths.clone()).unwrap_err();
+        let Error::InputUnavailable {
+            day,
+            year,
+            tried,
+            download: None,
+        } = &error
+        else {
+            panic!("expected the input to be unavailable, got {error:?}");
+        };
+        assert_eq!((*day, *year), (1, 2024));
+        assert_eq!(tried, &paths);
+        let message = error.to_string();
+        assert!(
+            paths
+                .iter()
+                .all(|path| message.contains(&path.display().to_string()))
+        );
+        assert!(message.contains("Enable the `aoc-client` feature"));
+    }
+
+    #[test]
+    fn reports_why_the_input_could_not_be_downloaded() {
+        use crate::Error;
+
+        let error = Error::InputUnavailable {
             day: 1,
-            part: 1,
-            year: None,
-            func: |s| s,
+            year: 2024,
+            tried: vec!["input/1_2024.txt".into()],
+            download: Some(Box::new(Error::ConfigMissing { key: "token" })),
         };
-        // This should call get_remote_input and panic
-        let _ = get_input(&entry, &None);
+        assert_eq!(error.exit_code(), 4);
+        let message = error.to_string();
+        assert!(message.starts_with(
+            "No input available for Day 1 of Year 2024: not found in input/1_2024.txt, \
+             and downloading it failed: AOC session cookie is missing."
+        ));
+        assert!(!message.contains("aoc-client"));
+    }
+
+    #[test]
+    fn reports_the_input_file_that_cannot_be_read() {
+        use crate::Error;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.txt");
+
+        let error = super::get_input(1, 2024, &Input::File(path.clone())).unwrap_err();
+        assert!(matches!(&error, Error::InputRead { path: p, .. } if *p == path));
+        assert_eq!(error.exit_code(), 1);
+        assert!(error.to_string().contains(&path.display().to_string()));
     }
 
     #[test]
-    #[should_panic(
-        expected = "Publishing answers requires the `aoc-client` feature to be enabled."
-    )]
     #[cfg(not(feature = "aoc-client"))]
-    fn publish_result_panics_without_aoc_client() {
+    fn publish_result_fails_without_aoc_client() {
         use crate::runner::publish_result;
+        use crate::{AocEntry, Error, Solver};
 
         let entry = AocEntry {
             day: 1,
             part: 1,
             year: None,
-            func: |s| s,
+            variant: None,
+            func: Solver::Raw(Ok),
+            example: None,
+            timeout: None,
+            name: "identity",
+            module_path: module_path!(),
+            file: file!(),
+            line: line!(),
+            doc: None,
         };
-        let _ = publish_result(&entry, "42");
+        let result = publish_result(&entry, 2024, "42");
+        assert!(matches!(result, Err(Error::PublishUnavailable)));
+    }
+
+    #[test]
+    #[cfg(feature = "aoc-client")]
+    fn finds_the_first_unsolved_part() {
+        use crate::runner::unsolved_part;
+
+        let answer = "<p>Your puzzle answer was <code>42</code>.</p>";
+        assert_eq!(unsolved_part("<article>--- Day 1 ---</article>"), Some(1));
+        assert_eq!(unsolved_part(answer), Some(2));
+        assert_eq!(unsolved_part(&answer.repeat(2)), None);
     }
 }
@@ -0,0 +1,172 @@
+//! Scaffolding of the module of a new day (`new`).
+//! The module is created from a template holding a `#[star]` stub for each
+//! part, whose commented-out `example` and `expected` keys are ready to check
+//! it against the example of the puzzle (`test`), and is declared in the
+//! `mod.rs` of its directory. Both the path of the module and the template can be overridden
+//! in the `new:` section of the config.
+
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+
+use crate::Error;
+use crate::config::{NewConfig, read_config, render};
+
+/// Where the module of a day is created when the config does not say.
+const DEFAULT_PATH: &str = "src/days/day{day:02}.rs";
+
+/// The template of the module of a day when the config does not give one.
+const DEFAULT_TEMPLATE: &str = r#"use aoc_star::star;
+
+#[star(
+    day = {day},
+    part = 1,
+    year = {year},
+    // example = "",
+    // expected = 0,
+)]
+fn part1(input: &str) -> u64 {
+    todo!("solve part 1 of day {day} with {} bytes of input", input.len())
+}
+
+#[star(
+    day = {day},
+    part = 2,
+    year = {year},
+    // example = "",
+    // expected = 0,
+)]
+fn part2(input: &str) -> u64 {
+    todo!("solve part 2 of day {day} with {} bytes of input", input.len())
+}
+"#;
+
+/// Creates the module of `day` for `year` and declares it in the `mod.rs`
+/// next to it. Returns the path of the new module.
+///
+/// # Errors
+///
+/// Returns [`Error::FileExists`] if the module already exists, and the
+/// errors of reading the config or the template, or of writing the files.
+pub(crate) fn new_day(day: u32, year: i32) -> Result<PathBuf, Error> {
+    let config = read_config()?.new.unwrap_or_default();
+    let path = PathBuf::from(render(
+        config.path.as_deref().unwrap_or(DEFAULT_PATH),
+        day,
+        year,
+    ));
+    if path.exists() {
+        return Err(Error::FileExists(path));
+    }
+
+    let template = template(&config)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, render(&template, day, year))?;
+    info!("Created {}", path.display());
+
+    declare_module(&path)?;
+    Ok(path)
+}
+
+/// The template given in the config, or the default one.
+fn template(config: &NewConfig) -> Result<String, Error> {
+    match &config.template {
+        Some(template) => Ok(std::fs::read_to_string(template)?),
+        None => Ok(DEFAULT_TEMPLATE.to_string()),
+    }
+}
+
+/// Declares the module at `path` in the `mod.rs` of its directory, creating
+/// it if needed.
+fn declare_module(path: &Path) -> Result<(), Error> {
+    let Some(module) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return Ok(());
+    };
+    let mod_rs = path.with_file_name("mod.rs");
+
+    let contents = if mod_rs.exists() {
+        std::fs::read_to_string(&mod_rs)?
+    } else {
+        warn!(
+            "Creating {}, remember to declare its module in your crate",
+            mod_rs.display()
+        );
+        String::new()
+    };
+    match with_module(&contents, module) {
+        Some(contents) => {
+            std::fs::write(&mod_rs, contents)?;
+            info!("Declared `{module}` in {}", mod_rs.display());
+        }
+        None => info!("`{module}` is already declared in {}", mod_rs.display()),
+    }
+    Ok(())
+}
+
+/// `contents` of a `mod.rs` with `module` declared after the last module, with
+/// the same visibility, or `None` if it is already declared.
+fn with_module(contents: &str, module: &str) -> Option<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    if lines.iter().any(|line| declaration(line) == Some(module)) {
+        return None;
+    }
+
+    let last = lines.iter().rposition(|line| declaration(line).is_some());
+    let visibility = match last {
+        Some(last) if !lines[last].trim().starts_with("pub ") => "",
+        _ => "pub ",
+    };
+    let mut lines: Vec<String> = lines.into_iter().map(str::to_string).collect();
+    let at = last.map_or(lines.len(), |last| last + 1);
+    lines.insert(at, format!("{visibility}mod {module};"));
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    Some(contents)
+}
+
+/// The module declared by `line`, if it is a `mod` (or `pub mod`) item.
+fn declaration(line: &str) -> Option<&str> {
+    let line = line.trim();
+    line.strip_prefix("pub ")
+        .unwrap_or(line)
+        .strip_prefix("mod ")
+        .and_then(|rest| rest.strip_suffix(';'))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_PATH, DEFAULT_TEMPLATE, render, with_module};
+
+    #[test]
+    fn renders_placeholders() {
+        assert_eq!(render(DEFAULT_PATH, 7, 2024), "src/days/day07.rs");
+
+        let module = render(DEFAULT_TEMPLATE, 3, 2024);
+        assert!(module.contains("    day = 3,\n    part = 1,\n    year = 2024,\n"));
+        assert!(module.contains("    day = 3,\n    part = 2,\n    year = 2024,\n"));
+        assert!(module.contains("    // example = \"\",\n    // expected = 0,\n)]"));
+        assert!(!module.contains("{year}"));
+        assert!(!module.contains("#[cfg(test)]"));
+    }
+
+    #[test]
+    fn declares_modules_after_the_last_one() {
+        assert_eq!(
+            with_module("pub mod day01;\npub mod day02;\n", "day03").as_deref(),
+            Some("pub mod day01;\npub mod day02;\npub mod day03;\n")
+        );
+        assert_eq!(
+            with_module("//! Days.\n\nmod day01;\n\nfn helper() {}\n", "day02").as_deref(),
+            Some("//! Days.\n\nmod day01;\nmod day02;\n\nfn helper() {}\n")
+        );
+        assert_eq!(
+            with_module("", "day01").as_deref(),
+            Some("pub mod day01;\n")
+        );
+        assert_eq!(with_module("pub mod day01;\n", "day01"), None);
+    }
+}
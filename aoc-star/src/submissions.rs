@@ -0,0 +1,569 @@
+//! History of the answers submitted to Advent of Code.
+//! Every submission is appended to `submissions.jsonl` in the answers
+//! directory (one JSON object per line) with its outcome, so that the runner can check an answer
+//! before submitting it: an answer that was already rejected, or that is out
+//! of the bounds given by previous "too high" and "too low" answers, is not
+//! submitted again, and neither is a part that was already solved.
+//! When Advent of Code asks to wait before submitting again, the time the
+//! puzzle unlocks is recorded too, and no answer is submitted for it before
+//! then: retrying early would only reset the timer.
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::config::answers_dir;
+use crate::{Error, Rejection};
+
+/// The outcome of submitting the answer of a part to Advent of Code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(into = "&'static str", try_from = "String")]
+pub(crate) enum Submission {
+    Correct,
+    Rejected(Rejection),
+}
+
+impl Submission {
+    /// The part that was submitted and how it went, given the result of
+    /// publishing (the submitted part, if any). `None` if nothing was
+    /// submitted, or if the submission failed before the answer was checked.
+    pub(crate) fn of(published: &Result<Option<u32>, Error>) -> Option<(u32, Submission)> {
+        match published {
+            Ok(part) => part.map(|part| (part, Submission::Correct)),
+            Err(Error::SubmissionRejected { part, reason, .. }) => {
+                Some((*part, Submission::Rejected(*reason)))
+            }
+            Err(_) => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Submission::Correct => "correct",
+            Submission::Rejected(Rejection::Incorrect) => "incorrect",
+            Submission::Rejected(Rejection::TooHigh) => "too-high",
+            Submission::Rejected(Rejection::TooLow) => "too-low",
+            Submission::Rejected(Rejection::TooRecent) => "too-recent",
+            Submission::Rejected(Rejection::WrongLevel) => "wrong-level",
+        }
+    }
+}
+
+impl From<Submission> for &'static str {
+    fn from(submission: Submission) -> Self {
+        submission.as_str()
+    }
+}
+
+impl TryFrom<String> for Submission {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ok(match s.as_str() {
+            "correct" => Submission::Correct,
+            "incorrect" => Submission::Rejected(Rejection::Incorrect),
+            "too-high" => Submission::Rejected(Rejection::TooHigh),
+            "too-low" => Submission::Rejected(Rejection::TooLow),
+            "too-recent" => Submission::Rejected(Rejection::TooRecent),
+            "wrong-level" => Submission::Rejected(Rejection::WrongLevel),
+            _ => return Err(format!("unknown submission outcome `{s}`")),
+        })
+    }
+}
+
+/// An answer submitted to Advent of Code.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Attempt {
+    pub year: i32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Submission,
+    pub at: DateTime<Utc>,
+    /// When Advent of Code accepts answers for the puzzle again, if it asked
+    /// to wait.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock: Option<DateTime<Utc>>,
+}
+
+impl Attempt {
+    /// Why this attempt shows that `answer` is wrong, if it does: it is the
+    /// same answer, and it was rejected as wrong, or this attempt was too high
+    /// (low) and `answer` is not lower (higher).
+    fn rules_out(&self, answer: &str) -> Option<Rejection> {
+        let Submission::Rejected(reason) = self.outcome else {
+            return None;
+        };
+        let ruled_out = match reason {
+            Rejection::TooRecent | Rejection::WrongLevel => false,
+            _ if self.answer == answer => true,
+            Rejection::TooHigh => compare(answer, &self.answer).is_some_and(|ord| ord.is_ge()),
+            Rejection::TooLow => compare(answer, &self.answer).is_some_and(|ord| ord.is_le()),
+            Rejection::Incorrect => false,
+        };
+        ruled_out.then_some(reason)
+    }
+}
+
+/// Compares two answers as numbers, if they both are.
+fn compare(answer: &str, other: &str) -> Option<std::cmp::Ordering> {
+    let answer: i128 = answer.trim().parse().ok()?;
+    let other: i128 = other.trim().parse().ok()?;
+    Some(answer.cmp(&other))
+}
+
+/// The range of answers that previous "too high" and "too low" attempts
+/// leave open for a part, both ends excluded.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Bounds {
+    pub above: Option<i128>,
+    pub below: Option<i128>,
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.above, self.below) {
+            (Some(above), Some(below)) => write!(f, "above {above} and below {below}"),
+            (Some(above), None) => write!(f, "above {above}"),
+            (None, Some(below)) => write!(f, "below {below}"),
+            (None, None) => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Every answer submitted so far, in order.
+#[derive(Debug, Default)]
+pub(crate) struct History(Vec<Attempt>);
+
+impl History {
+    /// The file holding the history of submissions.
+    fn path() -> Result<PathBuf, Error> {
+        Ok(answers_dir()?.join("submissions.jsonl"))
+    }
+
+    /// Reads the history of submissions, empty if there is none yet.
+    pub(crate) fn load() -> Result<Self, Error> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map(History)
+            .map_err(|source| Error::SubmissionsParse { path, source })
+    }
+
+    /// Adds an attempt to the history, appending it to the history file.
+    pub(crate) fn append(&mut self, attempt: Attempt) -> Result<(), Error> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let line = serde_json::to_string(&attempt).expect("attempts are valid JSON");
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{line}")?;
+        self.0.push(attempt);
+        Ok(())
+    }
+
+    /// The attempts for a part, in order.
+    fn attempts(&self, year: i32, day: u32, part: u32) -> impl Iterator<Item = &Attempt> {
+        self.0
+            .iter()
+            .filter(move |attempt| (attempt.year, attempt.day, attempt.part) == (year, day, part))
+    }
+
+    /// The answer Advent of Code accepted for a part, if it did.
+    pub(crate) fn accepted(&self, year: i32, day: u32, part: u32) -> Option<&str> {
+        self.attempts(year, day, part)
+            .find(|attempt| attempt.outcome == Submission::Correct)
+            .map(|attempt| attempt.answer.as_str())
+    }
+
+    /// The first unsolved part of a day, if the history can tell:
+    /// `Some(None)` if both parts were accepted, `Some(Some(2))` if only part
+    /// 1 was, and `None` if part 1 was not (it may have been solved without
+    /// this history).
+    pub(crate) fn unsolved_part(&self, year: i32, day: u32) -> Option<Option<u32>> {
+        self.accepted(year, day, 1)?;
+        Some(self.accepted(year, day, 2).is_none().then_some(2))
+    }
+
+    /// Checks that no previous attempt shows that `answer` is wrong, failing
+    /// with [`Error::KnownWrongAnswer`] for the first one that does.
+    pub(crate) fn check(&self, year: i32, day: u32, part: u32, answer: &str) -> Result<(), Error> {
+        let ruling_out = self
+            .attempts(year, day, part)
+            .find_map(|attempt| Some((attempt, attempt.rules_out(answer)?)));
+        match ruling_out {
+            Some((previous, reason)) => Err(Error::KnownWrongAnswer {
+                day,
+                part,
+                year,
+                answer: answer.to_string(),
+                previous: previous.answer.clone(),
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// When Advent of Code accepts answers for the puzzle of a day again,
+    /// if it is still locked at `now`. The cooldown applies to both parts.
+    pub(crate) fn unlock(&self, year: i32, day: u32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.0
+            .iter()
+            .filter(|attempt| (attempt.year, attempt.day) == (year, day))
+            .filter_map(|attempt| attempt.unlock)
+            .max()
+            .filter(|unlock| *unlock > now)
+    }
+
+    /// The bounds given by the "too high" and "too low" attempts for a part.
+    pub(crate) fn bounds(&self, year: i32, day: u32, part: u32) -> Bounds {
+        let mut bounds = Bounds::default();
+        for attempt in self.attempts(year, day, part) {
+            let Ok(answer) = attempt.answer.trim().parse::<i128>() else {
+                continue;
+            };
+            match attempt.outcome {
+                Submission::Rejected(Rejection::TooLow) => {
+                    bounds.above = bounds.above.max(Some(answer));
+                }
+                Submission::Rejected(Rejection::TooHigh) => {
+                    bounds.below = Some(bounds.below.map_or(answer, |below| below.min(answer)));
+                }
+                _ => {}
+            }
+        }
+        bounds
+    }
+}
+
+/// The time at which a puzzle unlocks, when Advent of Code asked to wait
+/// `wait` after a submission made at `at`.
+pub(crate) fn unlock_after(at: DateTime<Utc>, wait: Duration) -> DateTime<Utc> {
+    at + TimeDelta::from_std(wait).unwrap_or(TimeDelta::MAX)
+}
+
+/// Formats a time to wait the way Advent of Code does, e.g. `4m 12s`.
+pub(crate) fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s}s"),
+        (h, m, s) => format!("{h}h {m}m {s}s"),
+    }
+}
+
+#[cfg(feature = "aoc-client")]
+/// How a submission went, given the page Advent of Code answered with, and
+/// how long it asked to wait before submitting again, if it did.
+pub(crate) fn submission_outcome(html: &str) -> Option<(Submission, Option<Duration>)> {
+    let outcome = if html.contains("That's the right answer") {
+        Submission::Correct
+    } else if html.contains("That's not the right answer") {
+        Submission::Rejected(if html.contains("your answer is too high") {
+            Rejection::TooHigh
+        } else if html.contains("your answer is too low") {
+            Rejection::TooLow
+        } else {
+            Rejection::Incorrect
+        })
+    } else if html.contains("You gave an answer too recently") {
+        Submission::Rejected(Rejection::TooRecent)
+    } else if html.contains("You don't seem to be solving the right level") {
+        Submission::Rejected(Rejection::WrongLevel)
+    } else {
+        return None;
+    };
+    Some((outcome, parse_wait(html)))
+}
+
+#[cfg(feature = "aoc-client")]
+/// The time to wait before submitting again, from "You have 4m 12s left to
+/// wait" (after submitting too early) or "please wait 5 minutes before trying
+/// again" (after a wrong answer).
+fn parse_wait(html: &str) -> Option<Duration> {
+    let lowercase = html.to_lowercase();
+    if let Some((before, _)) = lowercase.split_once(" left to wait") {
+        let (_, left) = before.rsplit_once("you have ")?;
+        return left
+            .split_whitespace()
+            .try_fold(Duration::ZERO, |wait, token| {
+                let (value, unit) = if let Some(value) = token.strip_suffix('h') {
+                    (value, 3600)
+                } else if let Some(value) = token.strip_suffix('m') {
+                    (value, 60)
+                } else {
+                    (token.strip_suffix('s')?, 1)
+                };
+                let seconds = value.parse::<u64>().ok()?.checked_mul(unit)?;
+                wait.checked_add(Duration::from_secs(seconds))
+            });
+    }
+
+    let (_, after) = lowercase.split_once("please wait ")?;
+    let (count, rest) = after.split_once(' ')?;
+    if !rest.starts_with("minute") {
+        return None;
+    }
+    let minutes: u64 = match count {
+        "one" => 1,
+        count => count.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes.checked_mul(60)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u32, answer: &str, outcome: Submission) -> Attempt {
+        Attempt {
+            year: 2024,
+            day: 3,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            at: DateTime::from_timestamp(1_733_202_000, 0).unwrap(),
+            unlock: None,
+        }
+    }
+
+    #[test]
+    fn serializes_attempts_as_json() {
+        let attempt = attempt(1, "42", Submission::Rejected(Rejection::TooHigh));
+
+        let json = serde_json::to_string(&attempt).unwrap();
+        assert_eq!(
+            json,
+            r#"{"year":2024,"day":3,"part":1,"answer":"42","outcome":"too-high","at":"2024-12-03T05:00:00Z"}"#
+        );
+        assert_eq!(serde_json::from_str::<Attempt>(&json).unwrap(), attempt);
+        assert!(serde_json::from_str::<Attempt>(&json.replace("too-high", "maybe")).is_err());
+    }
+
+    #[test]
+    fn rules_out_known_wrong_answers() {
+        let history = History(vec![
+            attempt(1, "100", Submission::Rejected(Rejection::TooHigh)),
+            attempt(1, "10", Submission::Rejected(Rejection::TooLow)),
+            attempt(1, "50", Submission::Rejected(Rejection::Incorrect)),
+            attempt(1, "60", Submission::Rejected(Rejection::TooRecent)),
+            attempt(1, "90", Submission::Rejected(Rejection::TooHigh)),
+            attempt(2, "5", Submission::Rejected(Rejection::TooLow)),
+        ]);
+
+        let ruling_out = |answer| match history.check(2024, 3, 1, answer) {
+            Err(Error::KnownWrongAnswer {
+                previous, reason, ..
+            }) => Some((previous, reason)),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(()) => None,
+        };
+        assert_eq!(
+            ruling_out("50"),
+            Some(("50".to_string(), Rejection::Incorrect))
+        );
+        assert_eq!(
+            ruling_out("100"),
+            Some(("100".to_string(), Rejection::TooHigh))
+        );
+        assert_eq!(
+            ruling_out("95"),
+            Some(("90".to_string(), Rejection::TooHigh))
+        );
+        assert_eq!(ruling_out("7"), Some(("10".to_string(), Rejection::TooLow)));
+        assert_eq!(ruling_out("60"), None);
+        assert_eq!(ruling_out("42"), None);
+        assert_eq!(ruling_out("abc"), None);
+
+        assert_eq!(
+            history.bounds(2024, 3, 1),
+            Bounds {
+                above: Some(10),
+                below: Some(90)
+            }
+        );
+        assert_eq!(
+            history.bounds(2024, 3, 1).to_string(),
+            "above 10 and below 90"
+        );
+        assert_eq!(history.bounds(2024, 3, 2).to_string(), "above 5");
+        assert_eq!(history.bounds(2024, 4, 1), Bounds::default());
+    }
+
+    #[test]
+    fn finds_the_accepted_answer() {
+        let history = History(vec![
+            attempt(1, "41", Submission::Rejected(Rejection::Incorrect)),
+            attempt(1, "42", Submission::Correct),
+        ]);
+
+        assert_eq!(history.accepted(2024, 3, 1), Some("42"));
+        assert_eq!(history.accepted(2024, 3, 2), None);
+    }
+
+    #[test]
+    fn finds_the_unsolved_part() {
+        let mut history = History(vec![attempt(2, "7", Submission::Correct)]);
+        assert_eq!(history.unsolved_part(2024, 3), None);
+
+        history.0.push(attempt(1, "42", Submission::Correct));
+        assert_eq!(history.unsolved_part(2024, 3), Some(None));
+
+        history.0.remove(0);
+        assert_eq!(history.unsolved_part(2024, 3), Some(Some(2)));
+        assert_eq!(history.unsolved_part(2024, 4), None);
+    }
+
+    #[test]
+    fn finds_when_the_puzzle_unlocks() {
+        let at = DateTime::from_timestamp(1_733_202_000, 0).unwrap();
+        let mut history = History(vec![
+            attempt(1, "41", Submission::Rejected(Rejection::Incorrect)),
+            attempt(1, "42", Submission::Rejected(Rejection::TooRecent)),
+        ]);
+        history.0[0].unlock = Some(unlock_after(at, Duration::from_secs(60)));
+        history.0[1].unlock = Some(unlock_after(at, Duration::from_secs(252)));
+
+        let unlock = DateTime::from_timestamp(1_733_202_252, 0).unwrap();
+        assert_eq!(history.unlock(2024, 3, at), Some(unlock));
+        assert_eq!(history.unlock(2024, 4, at), None);
+        assert_eq!(history.unlock(2024, 3, unlock), None);
+
+        let json = serde_json::to_string(&history.0[1]).unwrap();
+        assert!(json.ends_with(r#""unlock":"2024-12-03T05:04:12Z"}"#));
+        let json = serde_json::to_string(&attempt(1, "43", Submission::Correct)).unwrap();
+        assert!(!json.contains("unlock"));
+    }
+
+    #[test]
+    fn formats_the_time_to_wait() {
+        assert_eq!(format_wait(Duration::from_secs(42)), "42s");
+        assert_eq!(format_wait(Duration::from_millis(251_200)), "4m 12s");
+        assert_eq!(format_wait(Duration::from_secs(3600)), "1h 0m 0s");
+    }
+
+    #[test]
+    fn finds_the_submitted_part() {
+        let rejected = Err(Error::SubmissionRejected {
+            day: 3,
+            part: 2,
+            year: 2024,
+            reason: Rejection::Incorrect,
+        });
+
+        assert_eq!(Submission::of(&Ok(None)), None);
+        assert_eq!(Submission::of(&Ok(Some(1))), Some((1, Submission::Correct)));
+        assert_eq!(
+            Submission::of(&rejected),
+            Some((2, Submission::Rejected(Rejection::Incorrect)))
+        );
+        assert_eq!(Submission::of(&Err(Error::PublishUnavailable)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "aoc-client")]
+    fn reads_the_submission_outcome() {
+        let outcome = |text| {
+            submission_outcome(&format!("<main><article><p>{text}</p></article></main>"))
+                .map(|(outcome, _)| outcome)
+        };
+
+        assert_eq!(
+            outcome("That's the right answer! You are one gold star closer."),
+            Some(Submission::Correct)
+        );
+        assert_eq!(
+            outcome("That's not the right answer; your answer is too high."),
+            Some(Submission::Rejected(Rejection::TooHigh))
+        );
+        assert_eq!(
+            outcome("That's not the right answer; your answer is too low."),
+            Some(Submission::Rejected(Rejection::TooLow))
+        );
+        assert_eq!(
+            outcome("That's not the right answer. If you're stuck, ..."),
+            Some(Submission::Rejected(Rejection::Incorrect))
+        );
+        assert_eq!(
+            outcome("You gave an answer too recently; you have to wait"),
+            Some(Submission::Rejected(Rejection::TooRecent))
+        );
+        assert_eq!(outcome("Something else"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "aoc-client")]
+    fn reads_the_time_to_wait() {
+        let too_recent = "<p>You gave an answer too recently; you have to wait after submitting \
+            an answer before trying again.  You have 4m 12s left to wait. \
+            <a href=\"/2024/day/3\">[Return to Day 3]</a></p>";
+        assert_eq!(
+            submission_outcome(too_recent),
+            Some((
+                Submission::Rejected(Rejection::TooRecent),
+                Some(Duration::from_secs(252))
+            ))
+        );
+        assert_eq!(
+            parse_wait("You have 30s left to wait."),
+            Some(Duration::from_secs(30))
+        );
+
+        let wrong = "<p>That's not the right answer; your answer is too low.  If you're stuck, \
+            make sure you're using the full input data. Please wait one minute before trying \
+            again. <a href=\"/2024/day/3\">[Return to Day 3]</a></p>";
+        assert_eq!(
+            submission_outcome(wrong),
+            Some((
+                Submission::Rejected(Rejection::TooLow),
+                Some(Duration::from_secs(60))
+            ))
+        );
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "aoc-client")]
+    fn ignores_malformed_times_to_wait() {
+        assert_eq!(parse_wait("You have 5é left to wait."), None);
+        assert_eq!(parse_wait("You have é left to wait."), None);
+        assert_eq!(parse_wait("You have 4x left to wait."), None);
+        assert_eq!(
+            parse_wait(&format!("You have {}h left to wait.", u64::MAX)),
+            None
+        );
+        assert_eq!(
+            parse_wait(&format!(
+                "You have {}s {}s left to wait.",
+                u64::MAX,
+                u64::MAX
+            )),
+            None
+        );
+        assert_eq!(
+            parse_wait(&format!(
+                "Please wait {} minutes before trying again",
+                u64::MAX
+            )),
+            None
+        );
+    }
+}
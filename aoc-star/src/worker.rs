@@ -0,0 +1,265 @@
+//! Execution of solutions on a worker thread, so that they can be given a
+//! time budget (`--timeout`, or `timeout` in the `#[star]` attribute).
+//! The worker owns the input of a day and runs its solutions one after the
+//! other, parsing the input at most once. A solution that does not finish
+//! within its budget (each run of it, when benchmarking) is abandoned: the
+//! thread cannot be stopped, so it keeps running in the background, holding
+//! its 8 MiB stack and burning a CPU core until the solution returns or the
+//! process exits. The next solution of the day gets a new worker (which
+//! parses the input again if needed). A panicking solution is
+//! reported as failed, and the worker goes on with the next one.
+//! Independent days can also be run concurrently (`--all --jobs N`), each on
+//! its own worker.
+
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::warn;
+
+use crate::panics::{self, Panic};
+use crate::runner::{DayInput, StarRun, run_star};
+use crate::{AocEntry, Error};
+
+/// Stack size of the workers, the default size of the main thread stack on
+/// Linux, so that recursive solutions behave as if run on the main thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The input of a day, whose solutions are run on a worker thread.
+pub(crate) struct WorkerInput {
+    raw: String,
+    /// Budget of the solutions that do not have their own.
+    timeout: Option<Duration>,
+    worker: Option<Worker>,
+}
+
+/// A thread running the solutions of a day on its input.
+struct Worker {
+    jobs: Sender<Job>,
+    events: Receiver<Event>,
+    handle: JoinHandle<()>,
+}
+
+/// What a worker reports while running a job.
+enum Event {
+    /// A run of a benchmark finished, the next one gets a new budget.
+    Ran,
+    /// The job is done.
+    Done(Box<StarRun>),
+}
+
+/// A solution to run (or benchmark, if `bench_runs` is given) on a worker.
+struct Job {
+    entry: &'static AocEntry,
+    year: i32,
+    bench_runs: Option<u32>,
+}
+
+impl WorkerInput {
+    /// The input `raw`, on which solutions without a budget of their own are
+    /// given `timeout`, if any.
+    pub(crate) fn new(raw: String, timeout: Option<Duration>) -> Self {
+        WorkerInput {
+            raw,
+            timeout,
+            worker: None,
+        }
+    }
+
+    /// Runs (or benchmarks) a solution, abandoning it once its budget is
+    /// exceeded (see the module documentation). When benchmarking, the budget
+    /// applies to each run.
+    ///
+    /// A panic of the solution is reported as [`Error::Panic`].
+    pub(crate) fn run(
+        &mut self,
+        entry: &'static AocEntry,
+        year: i32,
+        bench_runs: Option<u32>,
+    ) -> StarRun {
+        let raw = &self.raw;
+        let worker = self
+            .worker
+            .get_or_insert_with(|| Worker::spawn(entry.day, raw.clone()));
+        worker
+            .jobs
+            .send(Job {
+                entry,
+                year,
+                bench_runs,
+            })
+            .expect("the worker outlives its jobs");
+
+        let budget = entry.timeout.or(self.timeout);
+        let received = loop {
+            let event = match budget {
+                Some(budget) => worker.events.recv_timeout(budget),
+                None => worker
+                    .events
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match event {
+                Ok(Event::Ran) => {}
+                Ok(Event::Done(run)) => break Ok(*run),
+                Err(e) => break Err(e),
+            }
+        };
+        match received {
+            Ok(run) => run,
+            Err(RecvTimeoutError::Timeout) => {
+                let budget = budget.expect("only a budget can run out");
+                warn!(
+                    "Abandoning `{}` after {budget:?}, it keeps running in the background",
+                    entry.qualified_name()
+                );
+                // Dropping the worker detaches its thread, which stops once
+                // the solution returns
+                self.worker = None;
+                StarRun {
+                    day: entry.day,
+                    part: entry.part,
+                    year,
+                    variant: entry.variant,
+                    result: Err(Error::Timeout {
+                        day: entry.day,
+                        part: entry.part,
+                        year,
+                        solution: entry.qualified_name(),
+                        budget,
+                    }),
+                    duration: budget,
+                    parse: None,
+                    bench: None,
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let worker = self.worker.take().expect("the worker was just used");
+                match worker.handle.join() {
+                    Err(panic) => std::panic::resume_unwind(panic),
+                    Ok(()) => unreachable!("the worker stopped before running its job"),
+                }
+            }
+        }
+    }
+}
+
+impl Worker {
+    /// Starts a worker for `day`, running its jobs on `raw`.
+    fn spawn(day: u32, raw: String) -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+        let (event_sender, events) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name(format!("day {day}"))
+            .stack_size(STACK_SIZE)
+            .spawn(move || {
+                let mut input = DayInput::new(raw);
+                for job in job_receiver {
+                    // An abandoned benchmark notices it when it is done
+                    let ran = || drop(event_sender.send(Event::Ran));
+                    let run = panics::catch(|| {
+                        run_star(job.entry, job.year, &mut input, job.bench_runs, ran)
+                    })
+                    .unwrap_or_else(|panic| panicked(job.entry, job.year, panic));
+                    if event_sender.send(Event::Done(Box::new(run))).is_err() {
+                        // The solution was abandoned
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn a worker thread");
+        Worker {
+            jobs,
+            events,
+            handle,
+        }
+    }
+}
+
+/// The run of a solution that panicked.
+fn panicked(entry: &AocEntry, year: i32, panic: Panic) -> StarRun {
+    StarRun {
+        day: entry.day,
+        part: entry.part,
+        year,
+        variant: entry.variant,
+        result: Err(Error::Panic {
+            day: entry.day,
+            part: entry.part,
+            year,
+            solution: entry.qualified_name(),
+            message: panic.message,
+            location: panic.location,
+        }),
+        duration: Duration::ZERO,
+        parse: None,
+        bench: None,
+    }
+}
+
+/// Maps `items` with `f`, on up to `jobs` threads at once, keeping the results
+/// in the order of `items`.
+pub(crate) fn map_concurrently<T, R>(
+    items: Vec<T>,
+    jobs: usize,
+    f: impl Fn(T) -> R + Sync,
+) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+{
+    let jobs = jobs.min(items.len());
+    if jobs <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let threads: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let next = queue.lock().expect("the queue is not poisoned").next();
+                        let Some((index, item)) = next else {
+                            return results;
+                        };
+                        results.push((index, f(item)));
+                    }
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .flat_map(|thread| {
+                thread
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::map_concurrently;
+
+    #[test]
+    fn keeps_the_order_of_concurrent_results() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = map_concurrently(items.clone(), 4, |n| {
+            // The first items finish last
+            std::thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+
+        assert_eq!(map_concurrently(vec![1, 2], 1, |n| n + 1), [2, 3]);
+        assert!(map_concurrently(Vec::<u32>::new(), 8, |n| n).is_empty());
+    }
+}
@@ -4,4 +4,11 @@ fn ui_tests() {
 
     t.pass("tests/ui/star_ok.rs");
     t.compile_fail("tests/ui/star_invalid_args.rs");
+    t.compile_fail("tests/ui/star_missing_return.rs");
+    t.compile_fail("tests/ui/star_parsed_by_value.rs");
+    t.compile_fail("tests/ui/star_mut_input.rs");
+    t.compile_fail("tests/ui/star_slice_input.rs");
+    t.compile_fail("tests/ui/star_array_input.rs");
+    t.compile_fail("tests/ui/star_dyn_input.rs");
+    t.compile_fail("tests/ui/star_impl_input.rs");
 }
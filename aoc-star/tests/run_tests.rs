@@ -1,6 +1,13 @@
-use aoc_star::star;
-use aoc_star::test_helpers::CommandArgument;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
 use aoc_star::test_helpers::run_with_result;
+use aoc_star::test_helpers::{CommandArgument, PartSelection};
+use aoc_star::test_helpers::{
+    StarRun, bench_with_result, compare_variants, run_all, run_both_parts, run_examples,
+};
+use aoc_star::{AocEntry, Error, Example, Input, Runner};
+use aoc_star::{star, star_parser};
 use tempfile::NamedTempFile;
 
 #[star(day = 3, part = 1, year = 2024)]
@@ -18,6 +25,169 @@ fn example_day3_part1_none(_input: String) -> String {
     "answer-3-2-none".to_string()
 }
 
+/// Length of the input.
+///
+/// Counts bytes, not characters.
+#[star(day = 4, part = 1, year = 2024)]
+fn example_day4_part1(input: &str) -> usize {
+    input.len()
+}
+
+#[star(day = 5, part = 1, year = 2024)]
+fn example_day5_part1(input: &str) -> Result<u64, std::num::ParseIntError> {
+    input.trim().parse()
+}
+
+#[star(
+    day = 6,
+    part = 1,
+    year = 2024,
+    example = "1abc2\npqr3stu8vwx",
+    expected = 2
+)]
+fn example_day6_part1(input: &str) -> usize {
+    input.lines().count()
+}
+
+#[star(
+    day = 6,
+    part = 2,
+    year = 2024,
+    example_file = "tests/examples/06.txt",
+    expected = "two1nine"
+)]
+fn example_day6_part2(input: &str) -> String {
+    input.lines().next().unwrap_or_default().to_string()
+}
+
+#[star(day = 7, part = 1, year = 2024)]
+fn example_day7_part1_naive(_input: &str) -> u32 {
+    1
+}
+
+#[star(day = 7, part = 1, year = 2024)]
+fn example_day7_part1_copy(_input: &str) -> u32 {
+    1
+}
+
+#[star(day = 8, part = 1, year = 2024)]
+fn example_day8_part1(input: &str) -> usize {
+    input.split_whitespace().count()
+}
+
+#[star(day = 8, part = 1, year = 2024, variant = "bytes")]
+fn example_day8_part1_bytes(input: &str) -> usize {
+    input
+        .as_bytes()
+        .split(u8::is_ascii_whitespace)
+        .filter(|word| !word.is_empty())
+        .count()
+}
+
+#[star(day = 8, part = 2, year = 2024, variant = "right")]
+fn example_day8_part2_right(_input: &str) -> u32 {
+    1
+}
+
+#[star(day = 8, part = 2, year = 2024, variant = "wrong")]
+fn example_day8_part2_wrong(_input: &str) -> u32 {
+    2
+}
+
+struct Numbers(Vec<u64>);
+
+/// One number per line.
+#[star_parser(day = 10, year = 2024)]
+fn parse_day10(input: &str) -> Result<Numbers, std::num::ParseIntError> {
+    input
+        .lines()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map(Numbers)
+}
+
+#[star(day = 10, part = 1, year = 2024, example = "1\n2\n3", expected = 6)]
+fn example_day10_part1(numbers: &Numbers) -> u64 {
+    numbers.0.iter().sum()
+}
+
+#[star(day = 10, part = 2, year = 2024)]
+fn example_day10_part2(numbers: &Numbers) -> u64 {
+    numbers.0.iter().copied().max().unwrap_or_default()
+}
+
+#[star(day = 10, part = 2, year = 2024, variant = "sorted")]
+fn example_day10_part2_sorted(numbers: &Numbers) -> u64 {
+    let mut numbers = numbers.0.clone();
+    numbers.sort_unstable();
+    numbers.last().copied().unwrap_or_default()
+}
+
+#[star_parser(day = 11, year = 2024)]
+fn parse_day11(input: String) -> usize {
+    input.len()
+}
+
+#[star(day = 11, part = 1, year = 2024)]
+fn example_day11_part1(numbers: &Numbers) -> usize {
+    numbers.0.len()
+}
+
+#[star(day = 12, part = 1, year = 2023, timeout = "50ms")]
+fn example_day12_part1_runaway(_input: &str) -> u32 {
+    std::thread::sleep(Duration::from_secs(10));
+    1
+}
+
+#[star(day = 12, part = 2, year = 2023)]
+fn example_day12_part2_slow(input: &str) -> usize {
+    std::thread::sleep(Duration::from_millis(200));
+    input.len()
+}
+
+#[star(day = 13, part = 1, year = 2022)]
+fn example_day13_part1_panicking(input: &str) -> u8 {
+    input.as_bytes()[100]
+}
+
+#[star(day = 13, part = 2, year = 2022)]
+fn example_day13_part2(input: &str) -> usize {
+    input.len()
+}
+
+#[star(day = 14, part = 1, year = 2021)]
+fn example_day14_part1_slow(input: &str) -> usize {
+    std::thread::sleep(Duration::from_millis(30));
+    input.lines().count()
+}
+
+#[star(day = 14, part = 2, year = 2021)]
+fn example_day14_part2(input: &str) -> usize {
+    input.len()
+}
+
+#[star(day = 15, part = 1, year = 2021)]
+fn example_day15_part1(input: &str) -> usize {
+    std::thread::sleep(Duration::from_millis(10));
+    input.split_whitespace().count()
+}
+
+#[star(day = 16, part = 1, year = 2021)]
+fn example_day16_part1(input: &str) -> Result<u64, std::num::ParseIntError> {
+    input.lines().map(str::parse::<u64>).sum()
+}
+
+static DAY17_RUNS: AtomicU32 = AtomicU32::new(0);
+
+#[star(day = 17, part = 1, year = 2020)]
+fn example_day17_part1_slowing_down(input: &str) -> usize {
+    // Only the third run is slow
+    if DAY17_RUNS.fetch_add(1, Ordering::SeqCst) == 2 {
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    input.len()
+}
+
 fn generate_dummy_file(str: &str) -> NamedTempFile {
     let tmp = NamedTempFile::new().unwrap();
     let path = tmp.path().to_path_buf();
@@ -25,17 +195,30 @@ fn generate_dummy_file(str: &str) -> NamedTempFile {
     tmp
 }
 
+fn input_file(input: &NamedTempFile) -> Option<String> {
+    Some(input.path().to_str().unwrap().to_string())
+}
+
+/// Arguments running a part of a day of 2024 on `input`.
+fn args(day: u32, part: u32, input: &NamedTempFile) -> CommandArgument {
+    CommandArgument {
+        day: Some(day),
+        part: PartSelection::Single(part),
+        year: Some(2024),
+        input_file: input_file(input),
+        ..Default::default()
+    }
+}
+
 #[test]
 fn test_run_day3_part1_2024() {
     let tmp = generate_dummy_file("dummy input");
 
     let args = CommandArgument {
         day: Some(3),
-        part: 1,
         year: Some(2024),
-        input_file: Some(tmp.path().to_str().unwrap().to_string()),
-        publish: false,
-        setup: false,
+        input_file: input_file(&tmp),
+        ..Default::default()
     };
     println!("{:?}", tmp.path());
 
@@ -52,11 +235,10 @@ fn test_run_day3_part2_2024() {
 
     let args = CommandArgument {
         day: Some(3),
-        part: 2,
+        part: PartSelection::Single(2),
         year: Some(2024),
-        input_file: Some(tmp.path().to_str().unwrap().to_string()),
-        publish: false,
-        setup: false,
+        input_file: input_file(&tmp),
+        ..Default::default()
     };
 
     let result = run_with_result(args).unwrap();
@@ -70,11 +252,9 @@ fn test_run_day1_part1_none() {
 
     let args = CommandArgument {
         day: Some(1),
-        part: 1,
         year: Some(2025),
-        input_file: Some(tmp.path().to_str().unwrap().to_string()),
-        publish: false,
-        setup: false,
+        input_file: input_file(&tmp),
+        ..Default::default()
     };
 
     println!("{:?}", tmp.path());
@@ -87,15 +267,622 @@ fn test_run_day1_part1_none() {
 }
 
 #[test]
-#[should_panic(expected = "No solution found for Day 2 Part 1 of Year 2018")]
+fn test_run_borrowed_input_numeric_answer() {
+    let tmp = generate_dummy_file("dummy input");
+
+    let args = CommandArgument {
+        day: Some(4),
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        ..Default::default()
+    };
+
+    let result = run_with_result(args).unwrap();
+
+    assert_eq!(result, "11");
+}
+
+#[test]
+fn test_run_fallible_solution() {
+    let tmp = generate_dummy_file("42\n");
+
+    let args = CommandArgument {
+        day: Some(5),
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        ..Default::default()
+    };
+
+    let result = run_with_result(args).unwrap();
+
+    assert_eq!(result, "42");
+}
+
+#[test]
+fn test_run_fallible_solution_reports_error() {
+    let tmp = generate_dummy_file("not a number");
+
+    let args = CommandArgument {
+        day: Some(5),
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        ..Default::default()
+    };
+
+    let error = run_with_result(args).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Solution {
+            day: 5,
+            part: 1,
+            ..
+        }
+    ));
+    assert_eq!(error.exit_code(), 7);
+    assert_eq!(
+        error.to_string(),
+        "Solution `run_tests::example_day5_part1` for Day 5 Part 1 of Year 2024 failed: invalid digit found in string"
+    );
+}
+
+#[test]
 fn test_run_non_existent_solution() {
     let args = CommandArgument {
         day: Some(2),
-        part: 1,
         year: Some(2018),
-        input_file: None,
-        publish: false,
-        setup: false,
+        ..Default::default()
+    };
+    let error = run_with_result(args).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::NoSolution {
+            day: 2,
+            part: 1,
+            year: 2018
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "No solution found for Day 2 Part 1 of Year 2018"
+    );
+}
+
+#[test]
+fn test_run_all_resolves_entries_for_year() {
+    let tmp = generate_dummy_file("1\n5\n3\n");
+    let args = CommandArgument {
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        all: true,
+        ..Default::default()
+    };
+
+    let runs = run_all(args).unwrap();
+
+    let results: Vec<(u32, u32, Result<&str, i32>)> = runs
+        .iter()
+        .map(|run| {
+            let result = run.result.as_deref().map_err(Error::exit_code);
+            (run.day, run.part, result)
+        })
+        .collect();
+    assert_eq!(
+        results,
+        [
+            (1, 1, Ok("answer-3-2-none")),
+            (3, 1, Ok("answer-3-1-2024")),
+            (3, 2, Ok("answer-3-2-2024")),
+            (4, 1, Ok("6")),
+            (5, 1, Err(7)),
+            (6, 1, Ok("3")),
+            (6, 2, Ok("1")),
+            (7, 1, Err(8)),
+            (8, 1, Ok("3")),
+            (10, 1, Ok("9")),
+            (10, 2, Ok("5")),
+            (11, 1, Err(8))
+        ]
+    );
+    assert!(runs.iter().all(|StarRun { year, .. }| *year == 2024));
+}
+
+#[test]
+fn test_run_all_concurrently_keeps_the_order() {
+    let tmp = generate_dummy_file("1\n5\n3\n");
+    let args = CommandArgument {
+        year: Some(2021),
+        input_file: input_file(&tmp),
+        all: true,
+        ..Default::default()
+    };
+    let results = |args: CommandArgument| -> Vec<(u32, u32, String)> {
+        run_all(args)
+            .unwrap()
+            .into_iter()
+            .map(|run| (run.day, run.part, run.result.unwrap()))
+            .collect()
+    };
+    let expected = [
+        (1, 1, "answer-3-2-none".to_string()),
+        (14, 1, "3".to_string()),
+        (14, 2, "6".to_string()),
+        (15, 1, "3".to_string()),
+        (16, 1, "9".to_string()),
+    ];
+
+    assert_eq!(results(args.clone()), expected);
+    let concurrent = CommandArgument {
+        jobs: Some(4),
+        ..args.clone()
+    };
+    assert_eq!(results(concurrent), expected);
+    let serial_timing = CommandArgument {
+        jobs: Some(3),
+        serial_timing: true,
+        ..args
+    };
+    assert_eq!(results(serial_timing), expected);
+}
+
+#[test]
+fn test_bench_runs_solution_requested_times() {
+    let tmp = generate_dummy_file("dummy input");
+
+    let args = CommandArgument {
+        day: Some(4),
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        bench: Some(5),
+        ..Default::default()
+    };
+
+    let (result, stats) = bench_with_result(args).unwrap();
+
+    assert_eq!(result, "11");
+    assert_eq!(stats.runs, 5);
+    assert!(stats.min <= stats.median && stats.median <= stats.max);
+}
+
+#[test]
+fn test_examples_are_registered() {
+    let examples: Vec<(u32, Example)> = aoc_star::inventory::iter::<AocEntry>
+        .into_iter()
+        .filter(|entry| entry.day == 6)
+        .filter_map(|entry| entry.example.map(|example| (entry.part, example)))
+        .collect();
+
+    assert_eq!(examples.len(), 2);
+    assert!(examples.contains(&(
+        1,
+        Example {
+            input: "1abc2\npqr3stu8vwx",
+            expected: "2",
+        }
+    )));
+    assert!(examples.contains(&(
+        2,
+        Example {
+            input: "two1nine\neightwothree\n",
+            expected: "two1nine",
+        }
+    )));
+}
+
+#[test]
+fn test_solution_metadata_is_registered() {
+    let entry = aoc_star::inventory::iter::<AocEntry>
+        .into_iter()
+        .find(|entry| entry.day == 4 && entry.year == Some(2024))
+        .unwrap();
+
+    assert_eq!(entry.name, "example_day4_part1");
+    assert_eq!(entry.module_path, "run_tests");
+    assert_eq!(entry.qualified_name(), "run_tests::example_day4_part1");
+    assert!(entry.file.ends_with("run_tests.rs"));
+    assert_eq!(
+        entry.doc,
+        Some("Length of the input.\n\nCounts bytes, not characters.")
+    );
+    assert_eq!(entry.summary(), Some("Length of the input."));
+}
+
+#[test]
+fn test_run_conflicting_solutions() {
+    let tmp = generate_dummy_file("dummy input");
+
+    let args = CommandArgument {
+        day: Some(7),
+        year: Some(2024),
+        input_file: input_file(&tmp),
+        ..Default::default()
+    };
+
+    let error = run_with_result(args).unwrap_err();
+
+    let Error::ConflictingSolutions(conflicts) = &error else {
+        panic!("expected conflicting solutions, got {error:?}");
+    };
+    assert_eq!(conflicts.len(), 1);
+    let mut names: Vec<&str> = conflicts[0].entries.iter().map(|e| e.name).collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["example_day7_part1_copy", "example_day7_part1_naive"]
+    );
+    assert!(
+        conflicts[0]
+            .entries
+            .iter()
+            .all(|e| e.file.ends_with("run_tests.rs") && e.line > 0)
+    );
+    assert_eq!(error.exit_code(), 8);
+}
+
+#[test]
+fn test_run_variants() {
+    let tmp = generate_dummy_file("a few  words\n");
+
+    assert_eq!(run_with_result(args(8, 1, &tmp)).unwrap(), "3");
+    assert_eq!(
+        run_with_result(CommandArgument {
+            variant: Some("bytes".to_string()),
+            ..args(8, 1, &tmp)
+        })
+        .unwrap(),
+        "3"
+    );
+
+    let error = run_with_result(CommandArgument {
+        variant: Some("simd".to_string()),
+        ..args(8, 1, &tmp)
+    })
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "No variant `simd` of Day 8 Part 1 for Year 2024 (available: bytes, default)"
+    );
+    assert_eq!(error.exit_code(), 2);
+
+    let error = run_with_result(args(8, 2, &tmp)).unwrap_err();
+    assert!(matches!(error, Error::UnknownVariant { variant: None, .. }));
+}
+
+#[test]
+fn test_compare_variants() {
+    let tmp = generate_dummy_file("a few  words\n");
+
+    let runs = compare_variants(args(8, 1, &tmp)).unwrap();
+
+    let variants: Vec<Option<&str>> = runs.iter().map(|run| run.variant).collect();
+    assert_eq!(variants, [None, Some("bytes")]);
+    assert!(
+        runs.iter()
+            .all(|run| run.result.as_deref().ok() == Some("3"))
+    );
+}
+
+#[test]
+fn test_compare_disagreeing_variants() {
+    let tmp = generate_dummy_file("dummy input");
+
+    let runs = compare_variants(args(8, 2, &tmp)).unwrap();
+
+    let answers: Vec<&str> = runs
+        .iter()
+        .map(|run| run.result.as_deref().unwrap())
+        .collect();
+    assert_eq!(answers, ["1", "2"]);
+}
+
+#[test]
+fn test_run_parsed_solution() {
+    let tmp = generate_dummy_file("1\n5\n3\n");
+
+    assert_eq!(run_with_result(args(10, 1, &tmp)).unwrap(), "9");
+    assert_eq!(run_with_result(args(10, 2, &tmp)).unwrap(), "5");
+}
+
+#[test]
+fn test_parsed_input_is_shared() {
+    let tmp = generate_dummy_file("1\n5\n3\n");
+
+    let runs = compare_variants(args(10, 2, &tmp)).unwrap();
+
+    assert_eq!(runs.len(), 2);
+    assert!(runs[0].parse.is_some());
+    assert!(runs[1].parse.is_none());
+    assert!(
+        runs.iter()
+            .all(|run| run.result.as_deref().ok() == Some("5"))
+    );
+}
+
+#[test]
+fn test_run_both_parts() {
+    let tmp = generate_dummy_file("1\n5\n3\n");
+    let args = CommandArgument {
+        part: PartSelection::Both,
+        ..args(10, 1, &tmp)
+    };
+
+    let runs = run_both_parts(args).unwrap();
+
+    let answers: Vec<(u32, &str)> = runs
+        .iter()
+        .map(|run| (run.part, run.result.as_deref().unwrap()))
+        .collect();
+    assert_eq!(answers, [(1, "9"), (2, "5")]);
+    assert!(runs[0].parse.is_some());
+    assert!(runs[1].parse.is_none());
+}
+
+#[test]
+fn test_run_both_parts_with_missing_part() {
+    let tmp = generate_dummy_file("dummy input");
+    let both = CommandArgument {
+        part: PartSelection::Both,
+        ..args(4, 1, &tmp)
+    };
+
+    let runs = run_both_parts(both).unwrap();
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].result.as_deref().unwrap(), "11");
+    assert!(matches!(
+        runs[1].result,
+        Err(Error::NoSolution {
+            day: 4,
+            part: 2,
+            ..
+        })
+    ));
+
+    let both = CommandArgument {
+        part: PartSelection::Both,
+        ..args(9, 1, &tmp)
+    };
+    assert!(matches!(
+        run_both_parts(both),
+        Err(Error::NoSolution {
+            day: 9,
+            part: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_runner() {
+    let outcome = Runner::new()
+        .year(2024)
+        .day(4)
+        .input(Input::Text("hello".to_string()))
+        .run()
+        .unwrap();
+
+    assert_eq!(outcome.answer, "5");
+    assert_eq!(outcome.entry.name, "example_day4_part1");
+    assert_eq!(outcome.year_resolved, 2024);
+    assert_eq!(outcome.parse, None);
+
+    let tmp = generate_dummy_file("1\n5\n3\n");
+    let outcome = Runner::new()
+        .year(2024)
+        .day(10)
+        .part(2)
+        .variant("sorted")
+        .input(Input::File(tmp.path().to_path_buf()))
+        .run()
+        .unwrap();
+
+    assert_eq!(outcome.answer, "5");
+    assert_eq!(outcome.entry.variant, Some("sorted"));
+    assert!(outcome.parse.is_some());
+}
+
+#[test]
+fn test_runner_errors() {
+    let error = Runner::new().year(2024).run().unwrap_err();
+    assert!(matches!(error, Error::DayMissing));
+    assert_eq!(error.exit_code(), 2);
+
+    let runner = Runner::new()
+        .year(2024)
+        .day(5)
+        .input(Input::Text("five".to_string()));
+    assert_eq!(runner.resolve().unwrap().0.name, "example_day5_part1");
+    assert!(matches!(
+        runner.run(),
+        Err(Error::Solution {
+            day: 5,
+            part: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_incomplete_arguments_are_errors() {
+    let tmp = generate_dummy_file("dummy input");
+    let no_day = CommandArgument {
+        day: None,
+        ..args(4, 1, &tmp)
+    };
+    let both = CommandArgument {
+        part: PartSelection::Both,
+        ..args(4, 1, &tmp)
+    };
+
+    assert!(matches!(
+        run_with_result(no_day.clone()),
+        Err(Error::DayMissing)
+    ));
+    assert!(matches!(run_both_parts(no_day), Err(Error::DayMissing)));
+
+    let error = compare_variants(both.clone()).unwrap_err();
+    assert!(matches!(error, Error::SinglePartRequired));
+    assert_eq!(error.exit_code(), 2);
+    assert!(matches!(
+        run_with_result(both),
+        Err(Error::SinglePartRequired)
+    ));
+}
+
+#[test]
+fn test_run_examples() {
+    let runs = run_examples(None, Some(2024));
+
+    let solutions: Vec<(u32, u32, &str)> = runs
+        .iter()
+        .map(|run| (run.entry.day, run.entry.part, run.expected()))
+        .collect();
+    assert_eq!(solutions, [(6, 1, "2"), (6, 2, "two1nine"), (10, 1, "6")]);
+    assert!(runs.iter().all(|run| run.passed()), "{runs:?}");
+
+    assert_eq!(run_examples(Some(10), None).len(), 1);
+    assert!(run_examples(None, Some(2023)).is_empty());
+}
+
+#[test]
+fn test_parser_failure() {
+    let tmp = generate_dummy_file("1\nx\n");
+
+    let error = run_with_result(args(10, 1, &tmp)).unwrap_err();
+
+    assert!(matches!(error, Error::Parse { day: 10, .. }));
+    assert_eq!(error.exit_code(), 7);
+    assert_eq!(
+        error.to_string(),
+        "Parser `run_tests::parse_day10` for Day 10 of Year 2024 failed: \
+         invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_parsed_input_mismatch() {
+    let tmp = generate_dummy_file("1\n");
+
+    let error = run_with_result(args(11, 1, &tmp)).unwrap_err();
+
+    let Error::ParsedInputMismatch {
+        expected, found, ..
+    } = &error
+    else {
+        panic!("expected a parsed input mismatch, got {error:?}");
+    };
+    assert!(expected.ends_with("Numbers"));
+    assert_eq!(*found, "usize");
+    assert_eq!(error.exit_code(), 8);
+}
+
+#[test]
+fn test_solutions_exceeding_their_timeout() {
+    let runner = Runner::new()
+        .year(2023)
+        .day(12)
+        .input(Input::Text("input".to_string()));
+
+    let error = runner.clone().run().unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Timeout {
+            day: 12,
+            part: 1,
+            ..
+        }
+    ));
+    assert_eq!(error.exit_code(), 11);
+    assert_eq!(
+        error.to_string(),
+        "Solution `run_tests::example_day12_part1_runaway` for Day 12 Part 1 of Year 2023 \
+         did not finish within 50ms"
+    );
+
+    // The budget given to the runner only applies to solutions without one
+    let error = runner
+        .clone()
+        .part(2)
+        .timeout(Duration::from_millis(20))
+        .run()
+        .unwrap_err();
+    assert!(matches!(error, Error::Timeout { part: 2, .. }));
+    let error = runner.clone().timeout(Duration::from_secs(60)).run();
+    assert!(matches!(error, Err(Error::Timeout { part: 1, .. })));
+
+    assert_eq!(runner.part(2).run().unwrap().answer, "5");
+}
+
+#[test]
+fn test_timeout_applies_to_each_bench_run() {
+    let tmp = generate_dummy_file("a\nb\n");
+    let steady = CommandArgument {
+        year: Some(2021),
+        bench: Some(6),
+        timeout: Some(Duration::from_millis(100)),
+        ..args(14, 1, &tmp)
+    };
+
+    // Each run takes 30ms, longer than the budget all together
+    let (result, stats) = bench_with_result(steady).unwrap();
+
+    assert_eq!(result, "2");
+    assert_eq!(stats.runs, 6);
+
+    // A single run taking longer than the budget, though the runs all
+    // together do not take longer than it times their number
+    let slowing_down = CommandArgument {
+        year: Some(2020),
+        bench: Some(3),
+        timeout: Some(Duration::from_millis(100)),
+        ..args(17, 1, &tmp)
+    };
+    let error = bench_with_result(slowing_down).unwrap_err();
+    assert!(matches!(error, Error::Timeout { day: 17, budget, .. } if budget.as_millis() == 100));
+}
+
+#[test]
+fn test_timeout_does_not_stop_the_day() {
+    let tmp = generate_dummy_file("input");
+    let args = CommandArgument {
+        year: Some(2023),
+        part: PartSelection::Both,
+        ..args(12, 1, &tmp)
+    };
+
+    let runs = run_both_parts(args).unwrap();
+
+    assert!(matches!(runs[0].result, Err(Error::Timeout { .. })));
+    assert_eq!(runs[1].result.as_ref().unwrap(), "5");
+}
+
+#[test]
+fn test_panicking_solution_does_not_stop_the_batch() {
+    let tmp = generate_dummy_file("input");
+    let args = CommandArgument {
+        year: Some(2022),
+        part: PartSelection::Both,
+        ..args(13, 1, &tmp)
+    };
+
+    let runs = run_both_parts(args).unwrap();
+
+    let Err(error) = &runs[0].result else {
+        panic!("expected a panic, got {:?}", runs[0].result);
+    };
+    assert_eq!(error.exit_code(), 7);
+    let Error::Panic {
+        message, location, ..
+    } = error
+    else {
+        panic!("expected a panic, got {error:?}");
     };
-    let _ = run_with_result(args).unwrap();
+    assert!(message.starts_with("index out of bounds"), "{message}");
+    assert!(location.as_ref().unwrap().contains("run_tests.rs"));
+    assert!(error.to_string().starts_with(
+        "Solution `run_tests::example_day13_part1_panicking` for Day 13 Part 1 of Year 2022 \
+         panicked at "
+    ));
+    assert_eq!(runs[1].result.as_ref().unwrap(), "5");
 }
@@ -0,0 +1,8 @@
+use aoc_star::star;
+
+#[star(day = 7, part = 1)]
+fn bad(bytes: &[u8; 4]) -> usize {
+    bytes.len()
+}
+
+fn main() {}
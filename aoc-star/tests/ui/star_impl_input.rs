@@ -0,0 +1,8 @@
+use aoc_star::star;
+
+#[star(day = 7, part = 1)]
+fn bad(input: &impl std::fmt::Display) -> usize {
+    input.to_string().len()
+}
+
+fn main() {}
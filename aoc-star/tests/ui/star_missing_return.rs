@@ -0,0 +1,10 @@
+use aoc_star::star;
+
+#[star(day = 1, part = 2)]
+fn bad(s: &str) {
+    println!("{s}");
+}
+
+fn main() {
+    ()
+}
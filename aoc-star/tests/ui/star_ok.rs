@@ -1,10 +1,57 @@
-use aoc_star::star;
+use aoc_star::{star, star_parser};
 
 #[star(day = 1, part = 2, year = 2023)]
 fn example(s: String) -> String {
     s
 }
 
+#[star(day = 2, part = 1, year = 2023)]
+fn borrowed(s: &str) -> u64 {
+    s.len() as u64
+}
+
+#[star(day = 2, part = 2, year = 2023)]
+fn opaque(s: String) -> impl std::fmt::Display {
+    s.lines().count() as i128
+}
+
+#[star(day = 3, part = 1, year = 2023)]
+fn fallible(s: &str) -> Result<u32, std::num::ParseIntError> {
+    s.trim().parse()
+}
+
+#[star(day = 4, part = 1, year = 2023, example = "1\n2\n3", expected = 6)]
+fn with_example(s: &str) -> u32 {
+    s.lines().map(|line| line.parse::<u32>().unwrap()).sum()
+}
+
+#[star(day = 4, part = 1, year = 2023, variant = "fast")]
+fn with_variant(s: &str) -> usize {
+    s.lines().count()
+}
+
+struct Grid(Vec<Vec<u8>>);
+
+#[star_parser(day = 5, year = 2023)]
+fn parse(s: &str) -> Grid {
+    Grid(s.lines().map(|line| line.bytes().collect()).collect())
+}
+
+#[star_parser(day = 5)]
+fn parse_fallible(s: String) -> Result<Grid, std::io::Error> {
+    Ok(Grid(vec![s.into_bytes()]))
+}
+
+#[star(day = 5, part = 1, year = 2023, example = "ab\ncd", expected = 2)]
+fn parsed(grid: &Grid) -> usize {
+    grid.0.len()
+}
+
+#[star(day = 5, part = 2, year = 2023)]
+fn parsed_fallible(grid: &Grid) -> Result<usize, std::io::Error> {
+    Ok(grid.0.iter().map(Vec::len).sum())
+}
+
 fn main() {
     ()
 }
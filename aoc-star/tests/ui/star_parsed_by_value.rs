@@ -0,0 +1,17 @@
+use aoc_star::{star, star_parser};
+
+struct Grid(Vec<String>);
+
+#[star_parser(day = 7)]
+fn parse(input: &str) -> Grid {
+    Grid(input.lines().map(str::to_string).collect())
+}
+
+#[star(day = 7, part = 1)]
+fn bad(grid: Grid) -> usize {
+    grid.0.len()
+}
+
+fn main() {
+    ()
+}